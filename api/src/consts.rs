@@ -5,18 +5,24 @@ use solana_program::{pubkey, pubkey::Pubkey};  // 引入 Solana 的 Pubkey 类
 /// 允许初始化程序的权限地址。
 pub const INITIALIZER_ADDRESS: Pubkey = pubkey!("DKQEpKgGjNrLH7oF6qF6RQNdQi3nmvEbiAwEVEtAvKsd");
 
+/// 事件格式的版本。每当事件的布局发生变化时递增。
+pub const EVENT_VERSION: u32 = 3;
+
 /// 程序初始化时的基础奖励率。
 pub const INITIAL_BASE_REWARD_RATE: u64 = BASE_REWARD_RATE_MIN_THRESHOLD;
 
-/// 允许的最低基础奖励率，低于此值时应提高最低难度。
+/// 允许的最低基础奖励率的默认值，低于此值时应提高最低难度。
 pub const BASE_REWARD_RATE_MIN_THRESHOLD: u64 = 2u64.pow(5);
 
-/// 允许的最高基础奖励率，超过此值时应降低最低难度。
+/// 允许的最高基础奖励率的默认值，超过此值时应降低最低难度。
 pub const BASE_REWARD_RATE_MAX_THRESHOLD: u64 = 2u64.pow(8);
 
-/// 允许的垃圾邮件/活跃性容忍时间（秒）。
+/// 允许的垃圾邮件/活跃性容忍时间的默认值（秒）。
 pub const TOLERANCE: i64 = 5;
 
+/// 允许配置的最大容忍时间（秒）。
+pub const MAX_TOLERANCE: i64 = ONE_MINUTE / 2;
+
 /// 程序初始化时的最低难度。
 pub const INITIAL_MIN_DIFFICULTY: u32 = 1;
 
@@ -33,12 +39,27 @@ pub const ONE_ORE: u64 = 10u64.pow(TOKEN_DECIMALS as u32);
 /// 一分钟的持续时间（秒）。
 pub const ONE_MINUTE: i64 = 60;
 
+/// 一天的持续时间（秒）。
+pub const ONE_DAY: i64 = ONE_MINUTE * 60 * 24;
+
+/// 一周的持续时间（秒）。
+pub const ONE_WEEK: i64 = ONE_DAY * 7;
+
+/// 基点的分母。
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
 /// 程序周期中的分钟数。
 pub const EPOCH_MINUTES: i64 = 5;
 
-/// 程序周期的持续时间（秒）。
+/// 程序周期持续时间的默认值（秒）。
 pub const EPOCH_DURATION: i64 = ONE_MINUTE * EPOCH_MINUTES;
 
+/// 允许配置的最短周期持续时间（秒）。
+pub const MIN_EPOCH_DURATION: i64 = ONE_MINUTE;
+
+/// 允许配置的最长周期持续时间（秒）。
+pub const MAX_EPOCH_DURATION: i64 = ONE_DAY;
+
 /// 最大代币供应量（2100 万）。
 pub const MAX_SUPPLY: u64 = ONE_ORE * 21_000_000;
 
@@ -47,20 +68,158 @@ pub const TARGET_EPOCH_REWARDS: u64 = ONE_ORE * EPOCH_MINUTES as u64;
 
 /// 每个周期可以挖掘的最大 ORE 数量。
 /// 通货膨胀目标 ≈ 每分钟 1 ORE
-pub const MAX_EPOCH_REWARDS: u64 = TARGET_EPOCH_REWARDS * BUS_COUNT as u64;
+pub const MAX_EPOCH_REWARDS: u64 = TARGET_EPOCH_REWARDS * MAX_BUS_COUNT as u64;
 
 /// 每个 bus 允许在每个周期发行的 ORE 数量。
-pub const BUS_EPOCH_REWARDS: u64 = MAX_EPOCH_REWARDS / BUS_COUNT as u64;
+pub const BUS_EPOCH_REWARDS: u64 = MAX_EPOCH_REWARDS / MAX_BUS_COUNT as u64;
 
-/// bus 账户的数量，用于并行化挖矿操作。
-pub const BUS_COUNT: usize = 8;
+/// bus 账户数量的上限。bus 用于并行化挖矿操作，管理员可以通过 AddBus 指令逐个添加。
+pub const MAX_BUS_COUNT: usize = 16;
 
-/// 奖励率变化的平滑因子。奖励率在一个周期到下一个周期之间的变化不能超过此常量的倍数。
+/// 奖励率变化的平滑因子的默认值。奖励率在一个周期到下一个周期之间的变化不能超过此常量的倍数。
 pub const SMOOTHING_FACTOR: u64 = 2;
 
-// 断言 MAX_EPOCH_REWARDS 能被 BUS_COUNT 整除。
+/// 允许配置的最大平滑因子。
+pub const MAX_SMOOTHING_FACTOR: u64 = 16;
+
+/// 单个哈希奖励上限的默认值。
+pub const MAX_HASH_REWARD: u64 = ONE_ORE;
+
+/// 解除质押冷却时间的默认值（秒）。
+pub const UNSTAKE_COOLDOWN: i64 = ONE_DAY;
+
+/// 允许配置的最长解除质押冷却时间（秒）。
+pub const MAX_UNSTAKE_COOLDOWN: i64 = ONE_DAY * 30;
+
+/// 每个周期划入幸运奖池的发行量比例的默认值（基点）。
+pub const JACKPOT_RATE: u64 = 500;
+
+/// 允许配置的最大幸运奖池比例（基点）。
+pub const MAX_JACKPOT_RATE: u64 = 2_000;
+
+/// 直接赢得幸运奖池所需最低难度的默认值。
+pub const JACKPOT_DIFFICULTY: u64 = 32;
+
+/// 通过抽奖赢得幸运奖池的概率分母的默认值。
+pub const JACKPOT_ODDS: u64 = 100_000;
+
+/// 允许配置的最小抽奖概率分母。
+pub const MIN_JACKPOT_ODDS: u64 = 1_000;
+
+/// 允许配置的最大抽奖概率分母。
+pub const MAX_JACKPOT_ODDS: u64 = 1_000_000_000;
+
+/// 尾部发行量的默认值。默认不在最大供应量之后继续铸造。
+pub const TAIL_EMISSION: u64 = 0;
+
+/// 允许配置的最大尾部发行量。
+pub const MAX_TAIL_EMISSION: u64 = TARGET_EPOCH_REWARDS;
+
+/// 固定发行曲线：每个周期的目标奖励保持不变。
+pub const EMISSION_CURVE_FLAT: u64 = 0;
+
+/// 减半发行曲线：每隔固定的周期数，目标奖励减半。
+pub const EMISSION_CURVE_HALVING: u64 = 1;
+
+/// 指数衰减发行曲线：每隔固定的周期数，目标奖励按比例衰减。
+pub const EMISSION_CURVE_DECAY: u64 = 2;
+
+/// 发行曲线的默认值。
+pub const EMISSION_CURVE: u64 = EMISSION_CURVE_FLAT;
+
+/// 发行曲线每一步的周期数的默认值，约为一年。
+pub const EMISSION_INTERVAL: u64 = (ONE_DAY * 365 / EPOCH_DURATION) as u64;
+
+/// 指数衰减曲线每一步保留的发行量比例的默认值（基点）。
+pub const EMISSION_DECAY: u64 = 9_000;
+
+/// 重置赏金的默认值。
+pub const RESET_BOUNTY: u64 = ONE_ORE / 100;
+
+/// 允许配置的最大重置赏金。
+pub const MAX_RESET_BOUNTY: u64 = ONE_ORE;
+
+/// 领取手续费比例的默认值（基点）。默认不收取手续费。
+pub const CLAIM_FEE_BPS: u64 = 0;
+
+/// 允许配置的最大领取手续费比例（基点）。
+pub const MAX_CLAIM_FEE_BPS: u64 = 1_000;
+
+/// 回收闲置 proof 账户所需的闲置时间的默认值（秒）。
+pub const REAP_PERIOD: i64 = ONE_WEEK * 26;
+
+/// 允许配置的最短回收闲置时间（秒）。回收闲置时间为 0 时禁用回收。
+pub const MIN_REAP_PERIOD: i64 = ONE_WEEK * 4;
+
+/// 回收 proof 账户时支付给调用者的租金比例（基点），其余租金退还给授权者。
+pub const REAP_TIP_RATE: u64 = 500;
+
+/// 认证模式：通过空操作程序指令中携带的 proof 地址进行认证。
+pub const AUTH_MODE_NOOP: u64 = 0;
+
+/// 认证模式：通过 ed25519 预编译程序验证 proof 授权者对 (proof, challenge) 的签名。
+pub const AUTH_MODE_ED25519: u64 = 1;
+
+/// 认证模式：不进行认证，仅用于测试网络。
+pub const AUTH_MODE_NONE: u64 = 2;
+
+/// 认证模式的默认值。
+pub const AUTH_MODE: u64 = AUTH_MODE_NOOP;
+
+/// 每个 proof 账户的 miner set 中最多可以授权的矿工密钥数量。
+pub const MAX_MINERS: usize = 8;
+
+/// 一条批量挖矿指令中最多可以提交的解决方案数量。
+pub const MAX_MINE_BATCH: usize = 8;
+
+/// 铸币扩展标志位：元数据指针，指向铸币的元数据账户。仅 Token-2022 支持。
+pub const MINT_EXTENSION_METADATA_POINTER: u8 = 1 << 0;
+
+/// 铸币扩展标志位：转账手续费。仅 Token-2022 支持。
+pub const MINT_EXTENSION_TRANSFER_FEE: u8 = 1 << 1;
+
+/// 质押锁定档位的数量。档位 0 表示不锁定。
+pub const LOCK_TIER_COUNT: usize = 4;
+
+/// 每个锁定档位的锁定时长（秒）：不锁定、1 周、4 周和 12 周。
+pub const LOCK_DURATIONS: [i64; LOCK_TIER_COUNT] = [0, ONE_WEEK, ONE_WEEK * 4, ONE_WEEK * 12];
+
+/// 每个锁定档位的质押奖励乘数（基点）。档位 0 保持原有的线性质押奖励。
+pub const LOCK_MULTIPLIERS: [u64; LOCK_TIER_COUNT] = [10_000, 15_000, 20_000, 30_000];
+
+/// 暂停挖矿的标志位。
+pub const PAUSE_MINE: u64 = 1 << 0;
+
+/// 暂停领取的标志位。
+pub const PAUSE_CLAIM: u64 = 1 << 1;
+
+/// 暂停质押的标志位。
+pub const PAUSE_STAKE: u64 = 1 << 2;
+
+/// 暂停升级的标志位。
+pub const PAUSE_UPGRADE: u64 = 1 << 3;
+
+/// 暂停重置的标志位。
+pub const PAUSE_RESET: u64 = 1 << 4;
+
+/// 暂停解除质押的标志位。
+pub const PAUSE_UNSTAKE: u64 = 1 << 5;
+
+/// 暂停捐赠的标志位。
+pub const PAUSE_DONATE: u64 = 1 << 6;
+
+/// 所有可暂停指令的标志位。
+pub const PAUSE_ALL: u64 = PAUSE_MINE
+    | PAUSE_CLAIM
+    | PAUSE_STAKE
+    | PAUSE_UPGRADE
+    | PAUSE_RESET
+    | PAUSE_UNSTAKE
+    | PAUSE_DONATE;
+
+// 断言 MAX_EPOCH_REWARDS 能被 MAX_BUS_COUNT 整除。
 static_assertions::const_assert!(
-    (MAX_EPOCH_REWARDS / BUS_COUNT as u64) * BUS_COUNT as u64 == MAX_EPOCH_REWARDS
+    (MAX_EPOCH_REWARDS / MAX_BUS_COUNT as u64) * MAX_BUS_COUNT as u64 == MAX_EPOCH_REWARDS
 );
 
 /// bus 账户 PDA 的种子。
@@ -69,15 +228,36 @@ pub const BUS: &[u8] = b"bus";
 /// config 账户 PDA 的种子。
 pub const CONFIG: &[u8] = b"config";
 
+/// member 账户 PDA 的种子。
+pub const MEMBER: &[u8] = b"member";
+
+/// miner set 账户 PDA 的种子。
+pub const MINER_SET: &[u8] = b"miner_set";
+
+/// 事件授权者 PDA 的种子。
+pub const EVENT_AUTHORITY: &[u8] = b"event_authority";
+
+/// 手续费金库 PDA 的种子。
+pub const FEE_VAULT: &[u8] = b"fee_vault";
+
+/// 幸运奖池抽奖的种子。
+pub const JACKPOT: &[u8] = b"jackpot";
+
 /// 元数据账户 PDA 的种子。
 pub const METADATA: &[u8] = b"metadata";
 
 /// mint 账户 PDA 的种子。
 pub const MINT: &[u8] = b"mint";
 
+/// pool 账户 PDA 的种子。
+pub const POOL: &[u8] = b"pool";
+
 /// proof 账户 PDA 的种子。
 pub const PROOF: &[u8] = b"proof";
 
+/// session 账户 PDA 的种子。
+pub const SESSION: &[u8] = b"session";
+
 /// 财库账户 PDA 的种子。
 pub const TREASURY: &[u8] = b"treasury";
 
@@ -86,20 +266,11 @@ pub const MINT_NOISE: [u8; 16] = [
     89, 157, 88, 232, 243, 249, 197, 132, 199, 49, 19, 234, 91, 94, 150, 41,
 ];
 
-/// 代币元数据的名称。
-pub const METADATA_NAME: &str = "ORE";
-
-/// 代币元数据的股票符号。
-pub const METADATA_SYMBOL: &str = "ORE";
-
-/// 代币元数据的 URI。
-pub const METADATA_URI: &str = "https://ore.supply/metadata-v2.json";
-
 /// 用于常量 PDA 派生的程序 ID
 const PROGRAM_ID: [u8; 32] = unsafe { *(&crate::id() as *const Pubkey as *const [u8; 32]) };
 
-/// bus 账户的地址。
-pub const BUS_ADDRESSES: [Pubkey; BUS_COUNT] = array_const_fn_init![const_bus_address; 8];
+/// 所有可能的 bus 账户的地址。只有前 `Config.bus_count` 个 bus 已被创建。
+pub const BUS_ADDRESSES: [Pubkey; MAX_BUS_COUNT] = array_const_fn_init![const_bus_address; 16];
 
 /// 派生常量 bus 地址的函数。
 const fn const_bus_address(i: usize) -> Pubkey {
@@ -137,6 +308,21 @@ pub const TREASURY_ADDRESS: Pubkey =
 /// 财库账户的 bump 值，用于 CPI 调用。
 pub const TREASURY_BUMP: u8 = ed25519::derive_program_address(&[TREASURY], &PROGRAM_ID).1;
 
+/// 事件授权者的地址。只有程序自身能以该地址签名调用 Log 指令。
+pub const EVENT_AUTHORITY_ADDRESS: Pubkey =
+    Pubkey::new_from_array(ed25519::derive_program_address(&[EVENT_AUTHORITY], &PROGRAM_ID).0);
+
+/// 事件授权者的 bump 值，用于 CPI 调用。
+pub const EVENT_AUTHORITY_BUMP: u8 =
+    ed25519::derive_program_address(&[EVENT_AUTHORITY], &PROGRAM_ID).1;
+
+/// 手续费金库的地址。该 PDA 是手续费金库代币账户的授权者。
+pub const FEE_VAULT_ADDRESS: Pubkey =
+    Pubkey::new_from_array(ed25519::derive_program_address(&[FEE_VAULT], &PROGRAM_ID).0);
+
+/// 手续费金库的 bump 值，用于 CPI 调用。
+pub const FEE_VAULT_BUMP: u8 = ed25519::derive_program_address(&[FEE_VAULT], &PROGRAM_ID).1;
+
 /// 财库代币账户的地址。
 pub const TREASURY_TOKENS_ADDRESS: Pubkey = Pubkey::new_from_array(
     ed25519::derive_program_address(
@@ -150,5 +336,36 @@ pub const TREASURY_TOKENS_ADDRESS: Pubkey = Pubkey::new_from_array(
         .0,
 );
 
+/// 使用 Token-2022 程序部署时，财库代币账户的地址。
+pub const TREASURY_TOKENS_2022_ADDRESS: Pubkey = Pubkey::new_from_array(
+    ed25519::derive_program_address(
+        &[
+            unsafe { &*(&TREASURY_ADDRESS as *const Pubkey as *const [u8; 32]) },
+            unsafe { &*(&spl_token_2022::id() as *const Pubkey as *const [u8; 32]) },
+            unsafe { &*(&MINT_ADDRESS as *const Pubkey as *const [u8; 32]) },
+        ],
+        unsafe { &*(&spl_associated_token_account::id() as *const Pubkey as *const [u8; 32]) },
+    )
+        .0,
+);
+
+/// 返回给定代币程序下财库代币账户的地址。
+pub fn treasury_tokens_address(token_program: &Pubkey) -> Pubkey {
+    if token_program.eq(&spl_token_2022::id()) {
+        TREASURY_TOKENS_2022_ADDRESS
+    } else {
+        TREASURY_TOKENS_ADDRESS
+    }
+}
+
+/// 返回给定代币程序下手续费金库代币账户的地址，即手续费金库的关联代币账户。
+pub fn fee_vault_tokens_address(token_program: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address_with_program_id(
+        &FEE_VAULT_ADDRESS,
+        &MINT_ADDRESS,
+        token_program,
+    )
+}
+
 /// CU 优化的 Solana 空操作程序的地址。
 pub const NOOP_PROGRAM_ID: Pubkey = pubkey!("noop8ytexvkpCuqbf6FB89BSuNemHtPRqaNC31GWivW");
\ No newline at end of file
@@ -1,7 +1,133 @@
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey, rent::Rent,
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
     sysvar::Sysvar,
 };
+use spl_token_2022::{
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+    state::Mint,
+};
+
+use crate::{
+    consts::{EVENT_AUTHORITY, EVENT_AUTHORITY_ADDRESS, EVENT_AUTHORITY_BUMP, TOKEN_DECIMALS},
+    instruction::Log,
+};
+
+/// 通过自调用 Log 指令记录事件。
+///
+/// 与返回数据和程序日志不同，内部指令会完整保存在交易元数据中，且不会被后续的 CPI 覆盖。
+#[inline(always)]
+pub fn emit_event<'a, 'info>(
+    event_authority: &'a AccountInfo<'info>, // 事件授权者账户
+    program: &'a AccountInfo<'info>, // 本程序的账户
+    event: &[u8], // 事件数据
+) -> ProgramResult {
+    let mut data = Log {}.to_bytes();
+    data.extend_from_slice(event);
+    solana_program::program::invoke_signed(
+        &Instruction {
+            program_id: crate::id(),
+            accounts: vec![AccountMeta::new_readonly(EVENT_AUTHORITY_ADDRESS, true)],
+            data,
+        },
+        &[event_authority.clone(), program.clone()],
+        &[&[EVENT_AUTHORITY, &[EVENT_AUTHORITY_BUMP]]],
+    )
+}
+
+/// 通过给定的代币程序转账。使用 `transfer_checked`，因此同时支持 SPL Token 和 Token-2022 铸币。
+#[inline(always)]
+pub fn transfer_checked<'a, 'info>(
+    authority: &'a AccountInfo<'info>, // 转出账户的授权者
+    from: &'a AccountInfo<'info>, // 转出的代币账户
+    mint: &'a AccountInfo<'info>, // 代币的铸币账户
+    to: &'a AccountInfo<'info>, // 转入的代币账户
+    token_program: &'a AccountInfo<'info>, // 代币程序账户
+    amount: u64, // 转账金额
+) -> ProgramResult {
+    transfer_checked_signed(authority, from, mint, to, token_program, amount, &[])
+}
+
+/// 通过给定的代币程序，以 PDA 签名转账。
+#[inline(always)]
+pub fn transfer_checked_signed<'a, 'info>(
+    authority: &'a AccountInfo<'info>, // 转出账户的授权者
+    from: &'a AccountInfo<'info>, // 转出的代币账户
+    mint: &'a AccountInfo<'info>, // 代币的铸币账户
+    to: &'a AccountInfo<'info>, // 转入的代币账户
+    token_program: &'a AccountInfo<'info>, // 代币程序账户
+    amount: u64, // 转账金额
+    signer_seeds: &[&[&[u8]]], // 授权者 PDA 的种子
+) -> ProgramResult {
+    solana_program::program::invoke_signed(
+        &spl_token_2022::instruction::transfer_checked(
+            token_program.key,
+            from.key,
+            mint.key,
+            to.key,
+            authority.key,
+            &[],
+            amount,
+            TOKEN_DECIMALS,
+        )?,
+        &[
+            token_program.clone(),
+            from.clone(),
+            mint.clone(),
+            to.clone(),
+            authority.clone(),
+        ],
+        signer_seeds,
+    )
+}
+
+/// 通过给定的代币程序，以 PDA 签名铸造代币。
+#[inline(always)]
+pub fn mint_to_checked_signed<'a, 'info>(
+    mint: &'a AccountInfo<'info>, // 代币的铸币账户
+    to: &'a AccountInfo<'info>, // 接收铸造代币的账户
+    authority: &'a AccountInfo<'info>, // 铸币授权者
+    token_program: &'a AccountInfo<'info>, // 代币程序账户
+    amount: u64, // 铸造数量
+    signer_seeds: &[&[&[u8]]], // 授权者 PDA 的种子
+) -> ProgramResult {
+    solana_program::program::invoke_signed(
+        &spl_token_2022::instruction::mint_to_checked(
+            token_program.key,
+            mint.key,
+            to.key,
+            authority.key,
+            &[],
+            amount,
+            TOKEN_DECIMALS,
+        )?,
+        &[
+            token_program.clone(),
+            mint.clone(),
+            to.clone(),
+            authority.clone(),
+        ],
+        signer_seeds,
+    )
+}
+
+/// 计算向铸币转账给定金额时被扣除的转账手续费。没有转账手续费扩展的铸币返回 0。
+pub fn calculate_transfer_fee(mint: &AccountInfo<'_>, amount: u64) -> Result<u64, ProgramError> {
+    let mint_data = mint.data.borrow();
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() else {
+        return Ok(0);
+    };
+    let epoch = Clock::get()?.epoch;
+    transfer_fee_config
+        .calculate_epoch_fee(epoch, amount)
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
 
 /// 创建一个新的程序派生地址 (PDA)。
 #[inline(always)]
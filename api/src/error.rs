@@ -29,6 +29,66 @@ pub enum LuckycoinError {
 
     #[error("The proof does not match the expected account")]
     AuthFailed = 7,  // 证明与预期账户不匹配
+
+    #[error("The config parameters are out of bounds")]
+    ConfigInvalid = 8,  // 配置参数超出允许范围
+
+    #[error("The instruction is paused")]
+    Paused = 9,  // 指令已被暂停
+
+    #[error("The unstake amount cannot be greater than the staked balance")]
+    UnstakeTooLarge = 10,  // 解除质押金额不能大于质押余额
+
+    #[error("The stake is still in its unstake cooldown")]
+    UnstakeCooldown = 11,  // 质押仍处于解除质押冷却期
+
+    #[error("The stake is locked")]
+    StakeLocked = 12,  // 质押处于锁定期内
+
+    #[error("The lock cannot shorten an active lock")]
+    LockInvalid = 13,  // 锁定无效，不能缩短正在生效的锁定
+
+    #[error("The pool has no solution to submit")]
+    PoolEmpty = 14,  // 矿池当前轮次没有可提交的解决方案
+
+    #[error("The member accounts do not match the pool's contributed work")]
+    PoolMembersInvalid = 15,  // 成员账户与矿池记录的工作量不一致
+
+    #[error("The token metadata is invalid")]
+    MetadataInvalid = 16,  // 代币元数据无效
+
+    #[error("The bus accounts do not match the configured bus count")]
+    BusCountInvalid = 17,  // bus 账户与配置的 bus 数量不一致
+
+    #[error("The miner set is full")]
+    MinerSetFull = 18,  // miner set 已满
+
+    #[error("The signer is not an authorized miner of this proof")]
+    MinerNotAuthorized = 19,  // 签名者不是该 proof 的授权矿工
+
+    #[error("The session is expired or does not allow this action")]
+    SessionInvalid = 20,  // 会话已过期或不允许该操作
+
+    #[error("The claim exceeds the session's claim limit")]
+    SessionLimitExceeded = 21,  // 领取金额超过会话的领取上限
+
+    #[error("The mint extensions are not supported by the token program")]
+    MintExtensionsInvalid = 22,  // 代币程序不支持所选的铸币扩展
+
+    #[error("The proof cannot be reaped")]
+    ReapInvalid = 23,  // proof 账户仍有余额、仍然活跃或属于矿池
+
+    #[error("The calculation overflowed")]
+    Overflow = 24,  // 计算溢出
+
+    #[error("The pool has unsettled member rewards from the previous round")]
+    PoolUnsettled = 25,  // 矿池上一轮的成员奖励尚未结算完
+
+    #[error("The busses cannot be rebalanced")]
+    RebalanceInvalid = 26,  // 没有耗尽的 bus，或剩余奖励不足以分配到每个 bus
+
+    #[error("The proof still has linked accounts")]
+    LinkedAccountsOpen = 27,  // proof 账户仍有未关闭的关联账户
 }
 
 // 为 LuckycoinError 枚举生成错误处理相关的实现。
@@ -1,14 +1,480 @@
 use bytemuck::{Pod, Zeroable};  // 引入 bytemuck 库，以支持零拷贝数据结构
+use num_enum::TryFromPrimitive;  // 引入 num_enum 库以支持从原始类型转换
+use solana_program::{instruction::CompiledInstruction, pubkey::Pubkey};  // 引入 Solana 的指令和 Pubkey 类型
 use steel::*;  // 引入 steel 库，可能用于处理指令和账户元数据
 
+use crate::{
+    consts::{EVENT_AUTHORITY_ADDRESS, EVENT_VERSION},
+    instruction::LuckycoinInstruction,
+};
+
+/// 定义 Luckycoin 事件的枚举类型。
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, TryFromPrimitive)]
+pub enum LuckycoinEvent {
+    Mine = 0,
+    Jackpot = 1,
+    Claim = 2,
+    Stake = 3,
+    Unstake = 4,
+    Reset = 5,
+    Open = 6,
+    Close = 7,
+    Update = 8,
+    Upgrade = 9,
+    Donate = 10,
+}
+
+/// 每个事件开头的头部，用于识别事件类型和格式版本。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct EventHeader {
+    pub event_type: u32,  // 事件类型，对应 LuckycoinEvent
+    pub version: u32,     // 事件格式的版本
+}
+
+impl EventHeader {
+    /// 使用当前的事件格式版本创建事件头部。
+    pub fn new(event_type: LuckycoinEvent) -> Self {
+        Self {
+            event_type: event_type as u32,
+            version: EVENT_VERSION,
+        }
+    }
+}
+
 /// 定义 MineEvent 结构体，用于表示挖矿事件的数据。
 #[repr(C)]  // 指定为 C 语言兼容的内存布局
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]  // 实现相关 trait
 pub struct MineEvent {
+    pub header: EventHeader,  // 事件头部
     pub difficulty: u64,  // 当前挖矿的难度
     pub reward: u64,      // 挖矿奖励金额
     pub timing: i64,      // 事件的时间戳
 }
 
-// 为 MineEvent 结构体生成事件相关的实现。
-event!(MineEvent);
\ No newline at end of file
+/// 定义 JackpotEvent 结构体，用于表示赢得幸运奖池的事件数据。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct JackpotEvent {
+    pub header: EventHeader,  // 事件头部
+    pub authority: Pubkey,  // 赢得奖池的 proof 账户的授权者
+    pub bus: u64,           // 支付奖池的 bus 账户 ID
+    pub difficulty: u64,    // 获胜哈希的难度
+    pub amount: u64,        // 支付的奖池金额
+    pub timing: i64,        // 事件的时间戳
+}
+
+/// 定义 ClaimEvent 结构体，用于表示领取奖励的事件数据。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ClaimEvent {
+    pub header: EventHeader,  // 事件头部
+    pub authority: Pubkey,    // proof 账户的授权者
+    pub beneficiary: Pubkey,  // 接收代币的账户
+    pub amount: u64,          // 从收益中扣除的领取总额，包含手续费
+    pub earnings: u64,        // 领取后剩余的收益
+    pub fee: u64,             // 划入手续费金库的手续费
+    pub net: u64,             // 扣除手续费后转给受益人的金额
+}
+
+/// 定义 StakeEvent 结构体，用于表示质押的事件数据。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct StakeEvent {
+    pub header: EventHeader,  // 事件头部
+    pub authority: Pubkey,    // proof 账户的授权者
+    pub amount: u64,          // 质押的金额
+    pub stake: u64,           // 质押后的质押本金
+}
+
+/// 定义 UnstakeEvent 结构体，用于表示解除质押的事件数据。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct UnstakeEvent {
+    pub header: EventHeader,  // 事件头部
+    pub authority: Pubkey,    // proof 账户的授权者
+    pub beneficiary: Pubkey,  // 接收代币的账户
+    pub amount: u64,          // 解除质押的金额
+    pub stake: u64,           // 解除质押后的质押本金
+}
+
+/// 定义 ResetEvent 结构体，用于表示周期重置的事件数据。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ResetEvent {
+    pub header: EventHeader,    // 事件头部
+    pub base_reward_rate: u64,  // 新周期的基础奖励率
+    pub min_difficulty: u64,    // 新周期的最低难度
+    pub minted: u64,            // 铸造到国库的金额
+    pub top_balance: u64,       // 上一个周期观察到的最大质押余额
+    pub beneficiary: Pubkey,    // 接收重置赏金的账户
+    pub bounty: u64,            // 支付给受益人的赏金，已包含在铸造金额中
+    pub timing: i64,            // 重置的时间戳
+}
+
+/// 定义 OpenEvent 结构体，用于表示创建 proof 账户的事件数据。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct OpenEvent {
+    pub header: EventHeader,  // 事件头部
+    pub authority: Pubkey,    // proof 账户的授权者
+    pub miner: Pubkey,        // proof 账户的矿工
+    pub proof: Pubkey,        // proof 账户的地址
+    pub timing: i64,          // 创建的时间戳
+}
+
+/// 定义 CloseEvent 结构体，用于表示关闭 proof 账户的事件数据。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct CloseEvent {
+    pub header: EventHeader,  // 事件头部
+    pub authority: Pubkey,    // proof 账户的授权者
+    pub proof: Pubkey,        // proof 账户的地址
+    pub total_hashes: u64,    // proof 账户生命周期内提交的哈希总数
+    pub total_rewards: u64,   // proof 账户生命周期内获得的奖励总额
+}
+
+/// 定义 UpdateEvent 结构体，用于表示更新矿工的事件数据。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct UpdateEvent {
+    pub header: EventHeader,  // 事件头部
+    pub authority: Pubkey,    // proof 账户的授权者
+    pub miner: Pubkey,        // 新的矿工
+}
+
+/// 定义 UpgradeEvent 结构体，用于表示 v1 代币升级的事件数据。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct UpgradeEvent {
+    pub header: EventHeader,  // 事件头部
+    pub authority: Pubkey,    // 燃烧 v1 代币的签名者
+    pub beneficiary: Pubkey,  // 接收 v2 代币的账户
+    pub burned: u64,          // 燃烧的 v1 代币数量
+    pub minted: u64,          // 铸造的 v2 代币数量
+}
+
+/// 定义 DonateEvent 结构体，用于表示捐赠到奖励池的事件数据。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct DonateEvent {
+    pub header: EventHeader,  // 事件头部
+    pub donor: Pubkey,        // 捐赠的签名者
+    pub amount: u64,          // 签名者转出的金额
+    pub received: u64,        // 扣除转账手续费后记入奖励池的金额
+    pub reward_pool: u64,     // 捐赠后的奖励池余额
+}
+
+// 为每个事件结构体生成事件相关的实现。
+event!(MineEvent);
+event!(JackpotEvent);
+event!(ClaimEvent);
+event!(StakeEvent);
+event!(UnstakeEvent);
+event!(ResetEvent);
+event!(OpenEvent);
+event!(CloseEvent);
+event!(UpdateEvent);
+event!(UpgradeEvent);
+event!(DonateEvent);
+
+
+/// 从 Log 指令中解码出的事件。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DecodedEvent {
+    Mine(MineEvent),
+    Jackpot(JackpotEvent),
+    Claim(ClaimEvent),
+    Stake(StakeEvent),
+    Unstake(UnstakeEvent),
+    Reset(ResetEvent),
+    Open(OpenEvent),
+    Close(CloseEvent),
+    Update(UpdateEvent),
+    Upgrade(UpgradeEvent),
+    Donate(DonateEvent),
+}
+
+impl DecodedEvent {
+    /// 解码事件数据。未知的事件类型或版本返回 None。
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        let header = bytemuck::try_pod_read_unaligned::<EventHeader>(
+            data.get(..std::mem::size_of::<EventHeader>())?,
+        )
+        .ok()?;
+        if header.version.ne(&EVENT_VERSION) {
+            return None;
+        }
+        let event = match LuckycoinEvent::try_from(header.event_type).ok()? {
+            LuckycoinEvent::Mine => Self::Mine(read_event(data)?),
+            LuckycoinEvent::Jackpot => Self::Jackpot(read_event(data)?),
+            LuckycoinEvent::Claim => Self::Claim(read_event(data)?),
+            LuckycoinEvent::Stake => Self::Stake(read_event(data)?),
+            LuckycoinEvent::Unstake => Self::Unstake(read_event(data)?),
+            LuckycoinEvent::Reset => Self::Reset(read_event(data)?),
+            LuckycoinEvent::Open => Self::Open(read_event(data)?),
+            LuckycoinEvent::Close => Self::Close(read_event(data)?),
+            LuckycoinEvent::Update => Self::Update(read_event(data)?),
+            LuckycoinEvent::Upgrade => Self::Upgrade(read_event(data)?),
+            LuckycoinEvent::Donate => Self::Donate(read_event(data)?),
+        };
+        Some(event)
+    }
+}
+
+/// 读取指定类型的事件。事件数据的长度必须与事件结构体的大小一致。
+fn read_event<T: Pod>(data: &[u8]) -> Option<T> {
+    bytemuck::try_pod_read_unaligned::<T>(data).ok()
+}
+
+/// 从交易的内部指令中解码出所有 Luckycoin 事件。
+///
+/// `account_keys` 是交易的账户列表（包括从地址查找表加载的账户），`inner_instructions`
+/// 是交易元数据中所有的内部指令，按执行顺序排列。只有由事件授权者签名的 Log 指令会被解码。
+pub fn decode_events(
+    account_keys: &[Pubkey],
+    inner_instructions: &[CompiledInstruction],
+) -> Vec<DecodedEvent> {
+    inner_instructions
+        .iter()
+        .filter(|ix| {
+            account_keys
+                .get(ix.program_id_index as usize)
+                .is_some_and(|program_id| program_id.eq(&crate::id()))
+        })
+        .filter(|ix| {
+            ix.accounts
+                .first()
+                .and_then(|i| account_keys.get(*i as usize))
+                .is_some_and(|authority| authority.eq(&EVENT_AUTHORITY_ADDRESS))
+        })
+        .filter_map(|ix| match ix.data.split_first() {
+            Some((tag, data)) if tag.eq(&(LuckycoinInstruction::Log as u8)) => {
+                DecodedEvent::from_bytes(data)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 交易的账户列表：程序、事件授权者和一个无关账户。
+    fn account_keys() -> Vec<Pubkey> {
+        vec![crate::id(), EVENT_AUTHORITY_ADDRESS, Pubkey::new_unique()]
+    }
+
+    /// 构造由事件授权者签名的 Log 内部指令。
+    fn log_ix(event: &[u8]) -> CompiledInstruction {
+        let mut data = vec![LuckycoinInstruction::Log as u8];
+        data.extend_from_slice(event);
+        CompiledInstruction {
+            program_id_index: 0,
+            accounts: vec![1],
+            data,
+        }
+    }
+
+    fn decode(event: &[u8]) -> Vec<DecodedEvent> {
+        decode_events(&account_keys(), &[log_ix(event)])
+    }
+
+    #[test]
+    fn test_decode_mine_event() {
+        let mut event = MineEvent::zeroed();
+        event.header = EventHeader::new(LuckycoinEvent::Mine);
+        event.difficulty = 12;
+        event.reward = 1_000;
+        event.timing = -5;
+        assert_eq!(decode(event.to_bytes()), vec![DecodedEvent::Mine(event)]);
+    }
+
+    #[test]
+    fn test_decode_jackpot_event() {
+        let mut event = JackpotEvent::zeroed();
+        event.header = EventHeader::new(LuckycoinEvent::Jackpot);
+        event.authority = Pubkey::new_unique();
+        event.bus = 3;
+        event.amount = 500;
+        assert_eq!(decode(event.to_bytes()), vec![DecodedEvent::Jackpot(event)]);
+    }
+
+    #[test]
+    fn test_decode_claim_event() {
+        let mut event = ClaimEvent::zeroed();
+        event.header = EventHeader::new(LuckycoinEvent::Claim);
+        event.authority = Pubkey::new_unique();
+        event.beneficiary = Pubkey::new_unique();
+        event.amount = 100;
+        event.earnings = 7;
+        event.fee = 1;
+        event.net = 99;
+        assert_eq!(decode(event.to_bytes()), vec![DecodedEvent::Claim(event)]);
+    }
+
+    #[test]
+    fn test_claim_event_layout() {
+        // 手续费和净额追加在末尾，引入手续费之前的字段位置保持不变
+        assert_eq!(std::mem::size_of::<ClaimEvent>(), 8 + 32 + 32 + 8 * 4);
+        assert_eq!(bytemuck::offset_of!(ClaimEvent::zeroed(), ClaimEvent, amount), 72);
+        assert_eq!(bytemuck::offset_of!(ClaimEvent::zeroed(), ClaimEvent, earnings), 80);
+        assert_eq!(bytemuck::offset_of!(ClaimEvent::zeroed(), ClaimEvent, fee), 88);
+        assert_eq!(bytemuck::offset_of!(ClaimEvent::zeroed(), ClaimEvent, net), 96);
+    }
+
+    #[test]
+    fn test_decode_stake_event() {
+        let mut event = StakeEvent::zeroed();
+        event.header = EventHeader::new(LuckycoinEvent::Stake);
+        event.authority = Pubkey::new_unique();
+        event.amount = 10;
+        event.stake = 30;
+        assert_eq!(decode(event.to_bytes()), vec![DecodedEvent::Stake(event)]);
+    }
+
+    #[test]
+    fn test_decode_unstake_event() {
+        let mut event = UnstakeEvent::zeroed();
+        event.header = EventHeader::new(LuckycoinEvent::Unstake);
+        event.authority = Pubkey::new_unique();
+        event.beneficiary = Pubkey::new_unique();
+        event.amount = 10;
+        event.stake = 20;
+        assert_eq!(decode(event.to_bytes()), vec![DecodedEvent::Unstake(event)]);
+    }
+
+    #[test]
+    fn test_decode_reset_event() {
+        let mut event = ResetEvent::zeroed();
+        event.header = EventHeader::new(LuckycoinEvent::Reset);
+        event.base_reward_rate = 4;
+        event.min_difficulty = 8;
+        event.minted = 1_000;
+        event.beneficiary = Pubkey::new_unique();
+        event.bounty = 2;
+        assert_eq!(decode(event.to_bytes()), vec![DecodedEvent::Reset(event)]);
+    }
+
+    #[test]
+    fn test_decode_open_event() {
+        let mut event = OpenEvent::zeroed();
+        event.header = EventHeader::new(LuckycoinEvent::Open);
+        event.authority = Pubkey::new_unique();
+        event.miner = Pubkey::new_unique();
+        event.proof = Pubkey::new_unique();
+        assert_eq!(decode(event.to_bytes()), vec![DecodedEvent::Open(event)]);
+    }
+
+    #[test]
+    fn test_decode_close_event() {
+        let mut event = CloseEvent::zeroed();
+        event.header = EventHeader::new(LuckycoinEvent::Close);
+        event.authority = Pubkey::new_unique();
+        event.proof = Pubkey::new_unique();
+        event.total_hashes = 9;
+        assert_eq!(decode(event.to_bytes()), vec![DecodedEvent::Close(event)]);
+    }
+
+    #[test]
+    fn test_decode_update_event() {
+        let mut event = UpdateEvent::zeroed();
+        event.header = EventHeader::new(LuckycoinEvent::Update);
+        event.authority = Pubkey::new_unique();
+        event.miner = Pubkey::new_unique();
+        assert_eq!(decode(event.to_bytes()), vec![DecodedEvent::Update(event)]);
+    }
+
+    #[test]
+    fn test_decode_upgrade_event() {
+        let mut event = UpgradeEvent::zeroed();
+        event.header = EventHeader::new(LuckycoinEvent::Upgrade);
+        event.authority = Pubkey::new_unique();
+        event.beneficiary = Pubkey::new_unique();
+        event.burned = 5;
+        event.minted = 5;
+        assert_eq!(decode(event.to_bytes()), vec![DecodedEvent::Upgrade(event)]);
+    }
+
+    #[test]
+    fn test_decode_donate_event() {
+        let mut event = DonateEvent::zeroed();
+        event.header = EventHeader::new(LuckycoinEvent::Donate);
+        event.donor = Pubkey::new_unique();
+        event.amount = 100;
+        event.received = 99;
+        event.reward_pool = 1_099;
+        assert_eq!(decode(event.to_bytes()), vec![DecodedEvent::Donate(event)]);
+    }
+
+    #[test]
+    fn test_decode_truncated_event() {
+        let mut event = MineEvent::zeroed();
+        event.header = EventHeader::new(LuckycoinEvent::Mine);
+        let bytes = event.to_bytes();
+        assert!(decode(&bytes[..bytes.len() - 1]).is_empty());
+        assert!(decode(&bytes[..4]).is_empty());
+        assert!(decode(&[]).is_empty());
+
+        // 多余的数据同样无法解码
+        let mut extended = bytes.to_vec();
+        extended.push(0);
+        assert!(decode(&extended).is_empty());
+    }
+
+    #[test]
+    fn test_decode_unknown_event() {
+        // 未知的事件类型
+        let mut event = MineEvent::zeroed();
+        event.header = EventHeader::new(LuckycoinEvent::Mine);
+        event.header.event_type = u32::MAX;
+        assert!(decode(event.to_bytes()).is_empty());
+
+        // 未知的事件版本
+        event.header = EventHeader::new(LuckycoinEvent::Mine);
+        event.header.version = EVENT_VERSION + 1;
+        assert!(decode(event.to_bytes()).is_empty());
+    }
+
+    #[test]
+    fn test_decode_ignores_foreign_instructions() {
+        let mut event = MineEvent::zeroed();
+        event.header = EventHeader::new(LuckycoinEvent::Mine);
+        let keys = account_keys();
+
+        // 其他指令标签
+        let mut ix = log_ix(event.to_bytes());
+        ix.data[0] = LuckycoinInstruction::Log as u8 + 1;
+        assert!(decode_events(&keys, &[ix]).is_empty());
+
+        // 未由事件授权者签名
+        let mut ix = log_ix(event.to_bytes());
+        ix.accounts = vec![2];
+        assert!(decode_events(&keys, &[ix]).is_empty());
+
+        // 其他程序
+        let mut ix = log_ix(event.to_bytes());
+        ix.program_id_index = 2;
+        assert!(decode_events(&keys, &[ix]).is_empty());
+
+        // 无效的账户索引
+        let mut ix = log_ix(event.to_bytes());
+        ix.program_id_index = 9;
+        assert!(decode_events(&keys, &[ix]).is_empty());
+    }
+
+    #[test]
+    fn test_decode_multiple_events_in_order() {
+        let mut mine = MineEvent::zeroed();
+        mine.header = EventHeader::new(LuckycoinEvent::Mine);
+        let mut jackpot = JackpotEvent::zeroed();
+        jackpot.header = EventHeader::new(LuckycoinEvent::Jackpot);
+        let ixs = [log_ix(jackpot.to_bytes()), log_ix(mine.to_bytes())];
+        assert_eq!(
+            decode_events(&account_keys(), &ixs),
+            vec![DecodedEvent::Jackpot(jackpot), DecodedEvent::Mine(mine)]
+        );
+    }
+}
@@ -15,7 +15,40 @@ pub enum LuckycoinInstruction {
     Update = 6, 
     Upgrade = 7, 
     Health = 8, 
+    Unstake = 9,
+    Migrate = 10,
+    Lock = 11,
+    ProposeAuthority = 12,
+    AcceptAuthority = 13,
+    OpenPool = 14,
+    JoinPool = 15,
+    PoolSubmit = 16,
+    PoolMine = 17,
+    PoolClaim = 18,
+    Log = 19,
+    Donate = 20,
+    Rebalance = 21,
+    AddMiner = 22,
+    RemoveMiner = 23,
+    OpenSession = 24,
+    CloseSession = 25,
+    Audit = 26,
+    Quote = 27,
+    MineBatch = 28,
+    Reap = 29,
+    PoolSettle = 30,
+    CloseMinerSet = 31,
     Initialize = 100, 
+    SetConfig = 101,
+    Pause = 102,
+    Unpause = 103,
+    UpdateMetadata = 104,
+    AddBus = 105,
+    WithdrawFees = 106,
+    MigrateConfig = 107,
+    ProposeAdmin = 108,
+    AcceptAdmin = 109,
+    MigrateTreasury = 110,
 }
 
 /// 领取指令的结构体。
@@ -61,6 +94,148 @@ pub struct Stake {
     pub amount: [u8; 8],  // 质押的金额，使用 8 字节数组表示
 }
 
+/// 解除质押指令的结构体。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Unstake {
+    pub amount: [u8; 8],  // 解除质押的金额，使用 8 字节数组表示
+}
+
+/// 迁移指令的结构体，未包含额外字段。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Migrate {}
+
+/// 锁定质押指令的结构体。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Lock {
+    pub tier: u8,  // 锁定档位
+}
+
+/// 提议转移授权者指令的结构体，未包含额外字段。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ProposeAuthority {}
+
+/// 接受授权者转移指令的结构体。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct AcceptAuthority {
+    pub bump: u8,  // 新 proof 账户的 bump 值
+}
+
+/// 创建矿池指令的结构体。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct OpenPool {
+    pub pool_bump: u8,   // pool 账户的 bump 值
+    pub proof_bump: u8,  // 矿池 proof 账户的 bump 值
+}
+
+/// 加入矿池指令的结构体。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct JoinPool {
+    pub bump: u8,  // member 账户的 bump 值
+}
+
+/// 成员向矿池提交解决方案指令的结构体。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct PoolSubmit {
+    pub digest: [u8; 16],  // 挖矿结果的摘要
+    pub nonce: [u8; 8],    // 用于挖矿的随机数
+}
+
+/// 运营者提交矿池最好解决方案指令的结构体，未包含额外字段。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct PoolMine {}
+
+/// 成员从矿池领取奖励指令的结构体。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct PoolClaim {
+    pub amount: [u8; 8],  // 领取的金额
+}
+
+/// 记录事件指令的结构体。事件数据紧跟在指令标签之后，只能由程序自身通过 CPI 调用。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Log {}
+
+/// 捐赠指令的结构体。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Donate {
+    pub amount: [u8; 8],  // 捐赠的金额，使用 8 字节数组表示
+}
+
+/// 重新平衡 bus 奖励指令的结构体，未包含额外字段。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Rebalance {}
+
+/// 授权矿工密钥指令的结构体。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct AddMiner {
+    pub bump: u8,              // miner set 账户的 bump 值
+    pub expires_at: [u8; 8],   // 密钥的过期插槽，为 0 时永不过期
+}
+
+/// 移除矿工密钥指令的结构体，未包含额外字段。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct RemoveMiner {}
+
+/// 开启会话指令的结构体。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct OpenSession {
+    pub bump: u8,               // session 账户的 bump 值
+    pub expires_at: [u8; 8],    // 会话过期的时间戳
+    pub claim_limit: [u8; 8],   // 会话密钥最多可以领取的奖励数量
+}
+
+/// 关闭会话指令的结构体，未包含额外字段。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct CloseSession {}
+
+/// 审计指令的结构体，未包含额外字段。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Audit {}
+
+/// 报价指令的结构体。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Quote {
+    pub difficulty: [u8; 8],  // 要报价的哈希难度
+}
+
+/// 批量挖矿指令的结构体。指令数据之后依次跟随每个 proof 账户的 `Mine` 解决方案。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct MineBatch {}
+
+/// 回收闲置 proof 账户指令的结构体，未包含额外字段。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Reap {}
+
+/// 结算矿池成员奖励指令的结构体，未包含额外字段。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct PoolSettle {}
+
+/// 关闭 miner set 指令的结构体，未包含额外字段。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct CloseMinerSet {}
+
 /// 更新指令的结构体，未包含额外字段。
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
@@ -73,24 +248,103 @@ pub struct Upgrade {
     pub amount: [u8; 8],  // 升级的金额，使用 8 字节数组表示
 }
 
-/// 初始化指令的结构体，包含多个 bump 值。
+/// 初始化指令的结构体，包含多个 bump 值。bus 账户由 AddBus 指令单独创建。
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct Initialize {
-    pub bus_0_bump: u8,  // bus 0 的 bump 值
-    pub bus_1_bump: u8,  // bus 1 的 bump 值
-    pub bus_2_bump: u8,  // bus 2 的 bump 值
-    pub bus_3_bump: u8,  // bus 3 的 bump 值
-    pub bus_4_bump: u8,  // bus 4 的 bump 值
-    pub bus_5_bump: u8,  // bus 5 的 bump 值
-    pub bus_6_bump: u8,  // bus 6 的 bump 值
-    pub bus_7_bump: u8,  // bus 7 的 bump 值
     pub config_bump: u8,  // 配置的 bump 值
     pub metadata_bump: u8, // 元数据的 bump 值
     pub mint_bump: u8,    // 铸币的 bump 值
     pub treasury_bump: u8, // 财库的 bump 值
+    pub name: [u8; 32],    // 代币名称，UTF-8 编码，末尾以零填充
+    pub symbol: [u8; 16],  // 代币符号，UTF-8 编码，末尾以零填充
+    pub uri: [u8; 256],    // 代币元数据的 URI，UTF-8 编码，末尾以零填充
+    pub extensions: u8,    // 铸币扩展标志位，仅 Token-2022 支持
+    pub transfer_fee_basis_points: [u8; 2], // 转账手续费比例（基点）
+    pub maximum_fee: [u8; 8], // 单笔转账的最高手续费
+}
+
+/// 设置配置指令的结构体，仅管理员可调用。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetConfig {
+    pub epoch_duration: [u8; 8],                 // 周期持续时间（秒）
+    pub tolerance: [u8; 8],                      // 垃圾邮件/活跃性容忍时间（秒）
+    pub base_reward_rate_min_threshold: [u8; 8], // 最低基础奖励率阈值
+    pub base_reward_rate_max_threshold: [u8; 8], // 最高基础奖励率阈值
+    pub smoothing_factor: [u8; 8],               // 奖励率平滑因子
+    pub max_hash_reward: [u8; 8],                // 单个哈希的最大奖励
+    pub unstake_cooldown: [u8; 8],               // 解除质押的冷却时间（秒）
+    pub jackpot_rate: [u8; 8],                   // 划入幸运奖池的发行量比例（基点）
+    pub jackpot_difficulty: [u8; 8],             // 直接赢得幸运奖池的最低难度
+    pub jackpot_odds: [u8; 8],                   // 抽奖赢得幸运奖池的概率分母
+    pub tail_emission: [u8; 8],                  // 达到最大供应量之后每个周期的尾部发行量
+    pub emission_curve: [u8; 8],                 // 发行曲线的类型
+    pub emission_interval: [u8; 8],              // 发行曲线每一步的周期数
+    pub emission_decay: [u8; 8],                 // 指数衰减曲线每一步保留的比例（基点）
+    pub reset_bounty: [u8; 8],                   // 支付给重置受益人的赏金
+    pub auth_mode: [u8; 8],                      // 挖矿时认证 proof 账户的方式
+    pub claim_fee_bps: [u8; 8],                  // 领取手续费比例（基点）
+    pub reap_period: [u8; 8],                    // 回收闲置 proof 账户所需的闲置时间（秒）
+}
+
+/// 暂停指令的结构体，仅管理员可调用。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Pause {
+    pub flags: [u8; 8],  // 需要暂停的指令标志位
+}
+
+/// 恢复指令的结构体，仅管理员可调用。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Unpause {
+    pub flags: [u8; 8],  // 需要恢复的指令标志位
 }
 
+/// 更新代币元数据指令的结构体，仅管理员可调用。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct UpdateMetadata {
+    pub name: [u8; 32],    // 代币名称，UTF-8 编码，末尾以零填充
+    pub symbol: [u8; 16],  // 代币符号，UTF-8 编码，末尾以零填充
+    pub uri: [u8; 256],    // 代币元数据的 URI，UTF-8 编码，末尾以零填充
+}
+
+/// 添加 bus 指令的结构体，仅管理员可调用。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct AddBus {
+    pub bump: u8,  // 新 bus 账户的 bump 值
+}
+
+/// 提取手续费指令的结构体，仅管理员可调用。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct WithdrawFees {
+    pub amount: [u8; 8],  // 从手续费金库提取的金额
+}
+
+/// 迁移配置指令的结构体，未包含额外字段。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct MigrateConfig {}
+
+/// 提议转移管理员指令的结构体，仅管理员可调用。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ProposeAdmin {}
+
+/// 接受管理员转移指令的结构体，未包含额外字段。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct AcceptAdmin {}
+
+/// 迁移财库指令的结构体，未包含额外字段。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct MigrateTreasury {}
+
 // 为每个指令类型生成指令相关的实现。
 instruction!(LuckycoinInstruction, Claim);
 instruction!(LuckycoinInstruction, Close);
@@ -101,4 +355,37 @@ instruction!(LuckycoinInstruction, Stake);
 instruction!(LuckycoinInstruction, Update);
 instruction!(LuckycoinInstruction, Upgrade);
 instruction!(LuckycoinInstruction, Health);
-instruction!(LuckycoinInstruction, Initialize);
\ No newline at end of file
+instruction!(LuckycoinInstruction, Unstake);
+instruction!(LuckycoinInstruction, Migrate);
+instruction!(LuckycoinInstruction, Lock);
+instruction!(LuckycoinInstruction, ProposeAuthority);
+instruction!(LuckycoinInstruction, AcceptAuthority);
+instruction!(LuckycoinInstruction, OpenPool);
+instruction!(LuckycoinInstruction, JoinPool);
+instruction!(LuckycoinInstruction, PoolSubmit);
+instruction!(LuckycoinInstruction, PoolMine);
+instruction!(LuckycoinInstruction, PoolClaim);
+instruction!(LuckycoinInstruction, Log);
+instruction!(LuckycoinInstruction, Donate);
+instruction!(LuckycoinInstruction, Rebalance);
+instruction!(LuckycoinInstruction, AddMiner);
+instruction!(LuckycoinInstruction, RemoveMiner);
+instruction!(LuckycoinInstruction, OpenSession);
+instruction!(LuckycoinInstruction, CloseSession);
+instruction!(LuckycoinInstruction, Audit);
+instruction!(LuckycoinInstruction, Quote);
+instruction!(LuckycoinInstruction, MineBatch);
+instruction!(LuckycoinInstruction, Reap);
+instruction!(LuckycoinInstruction, PoolSettle);
+instruction!(LuckycoinInstruction, CloseMinerSet);
+instruction!(LuckycoinInstruction, Initialize);
+instruction!(LuckycoinInstruction, SetConfig);
+instruction!(LuckycoinInstruction, Pause);
+instruction!(LuckycoinInstruction, Unpause);
+instruction!(LuckycoinInstruction, UpdateMetadata);
+instruction!(LuckycoinInstruction, AddBus);
+instruction!(LuckycoinInstruction, WithdrawFees);
+instruction!(LuckycoinInstruction, MigrateConfig);
+instruction!(LuckycoinInstruction, ProposeAdmin);
+instruction!(LuckycoinInstruction, AcceptAdmin);
+instruction!(LuckycoinInstruction, MigrateTreasury);
\ No newline at end of file
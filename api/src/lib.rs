@@ -6,11 +6,15 @@ pub mod state;
 pub mod event;
 pub mod loaders;
 pub mod cpi;
+pub mod report;
+pub mod reward;
 
 pub mod prelude {
     pub use crate::consts::*;
     pub use crate::error::*;
     pub use crate::instruction::*;
+    pub use crate::report::*;
+    pub use crate::reward::*;
     pub use crate::sdk::*;
     pub use crate::state::*;
 }
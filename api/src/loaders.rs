@@ -1,12 +1,14 @@
 use solana_program::{account_info::AccountInfo, msg, program_error::ProgramError, pubkey::Pubkey};
-use solana_program::program_pack::Pack;
-use spl_token::state::Mint;
+use spl_token_2022::{
+    extension::StateWithExtensions,
+    state::{Account, Mint},
+};
 // 引入 Solana 的相关模块
 use steel::*;  // 引入 steel 库，可能用于处理指令和账户元数据
 
 use crate::{  // 引入当前模块中的常量和状态定义
               consts::*,
-              state::{Bus, Config, Proof, Treasury},
+              state::{Bus, Config, Member, MinerSet, Pool, Proof, Session, Treasury},
 };
 
 /// 检查账户是否为签名者
@@ -58,7 +60,7 @@ pub fn load_account(
 }
 
 /// Errors if:
-/// - Owner is not SPL token program.
+/// - Owner is not the SPL token or Token-2022 program.
 /// - Address does not match the expected mint address.
 /// - Data is empty.
 /// - Data cannot deserialize into a mint account.
@@ -68,7 +70,7 @@ pub fn load_mint(
     address: Pubkey,
     is_writable: bool,
 ) -> Result<(), ProgramError> {
-    if info.owner.ne(&spl_token::id()) {
+    if !is_token_program(info.owner) {
         return Err(ProgramError::InvalidAccountOwner);
     }
 
@@ -80,7 +82,7 @@ pub fn load_mint(
         return Err(ProgramError::UninitializedAccount);
     }
 
-    Mint::unpack(&info.data.borrow())?;
+    StateWithExtensions::<Mint>::unpack(&info.data.borrow())?;
 
     if is_writable && !info.is_writable {
         return Err(ProgramError::InvalidAccountData);
@@ -133,7 +135,7 @@ pub fn load_system_account(info: &AccountInfo<'_>, is_writable: bool) -> Result<
 }
 
 /// Errors if:
-/// - Owner is not SPL token program.
+/// - Owner is not the SPL token or Token-2022 program.
 /// - Data is empty.
 /// - Data cannot deserialize into a token account.
 /// - Token account owner does not match the expected owner address.
@@ -145,8 +147,8 @@ pub fn load_token_account(
     mint: &Pubkey,            // 预期的铸币地址
     is_writable: bool,        // 是否期望账户可写
 ) -> Result<(), ProgramError> {
-    // 检查账户的所有者是否为 SPL Token 程序
-    if info.owner.ne(&spl_token::id()) {
+    // 检查账户的所有者是否为 SPL Token 或 Token-2022 程序
+    if !is_token_program(info.owner) {
         return Err(ProgramError::InvalidAccountOwner);  // 如果不是，返回无效账户所有者的错误
     }
 
@@ -157,7 +159,7 @@ pub fn load_token_account(
 
     // 获取账户数据并尝试反序列化为 SPL Token 账户
     let account_data = info.data.borrow();
-    let account = spl_token::state::Account::unpack(&account_data)?;
+    let account = StateWithExtensions::<Account>::unpack(&account_data)?.base;
 
     // 检查代币账户的铸币地址是否匹配预期的铸币地址
     if account.mint.ne(&mint) {
@@ -198,6 +200,26 @@ pub fn load_program(info: &AccountInfo<'_>, key: Pubkey) -> Result<(), ProgramEr
     Ok(())  // 所有检查通过，返回成功
 }
 
+/// 加载并验证代币程序账户，SPL Token 和 Token-2022 程序均可。
+pub fn load_token_program(info: &AccountInfo<'_>) -> Result<(), ProgramError> {
+    // 检查账户地址是否为受支持的代币程序
+    if !is_token_program(info.key) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // 检查账户是否可执行
+    if !info.executable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// 检查给定地址是否为受支持的代币程序。
+fn is_token_program(key: &Pubkey) -> bool {
+    key.eq(&spl_token::id()) || key.eq(&spl_token_2022::id())
+}
+
 /// 加载 bus 账户并进行验证。
 /// 错误条件：
 pub fn load_bus(info: &AccountInfo<'_>, id: u64, is_writable: bool) -> Result<(), ProgramError> {
@@ -295,6 +317,32 @@ pub fn load_config(info: &AccountInfo<'_>, is_writable: bool) -> Result<(), Prog
     Ok(())
 }
 
+/// 加载 config 账户并验证其管理员。
+/// 错误条件：
+/// - 账户的所有者不是 Luckycoin 程序。
+/// - 账户地址不是 config 地址。
+/// - 账户数据为空，或判别符不是 Config。
+/// - 期望可写但账户不可写。
+/// - 账户数据无法按当前布局反序列化为 Config（例如尚未通过 MigrateConfig 迁移）。
+/// - 配置的管理员与给定的地址不匹配。
+pub fn load_config_with_admin(
+    info: &AccountInfo<'_>,
+    admin: &Pubkey,
+    is_writable: bool,
+) -> Result<(), ProgramError> {
+    // 复用 config 账户的基本验证
+    load_config(info, is_writable)?;
+
+    // 检查配置的管理员是否匹配
+    let config_data = info.data.borrow();
+    let config = Config::try_from_bytes(&config_data)?;
+    if config.admin.ne(admin) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
 /// 加载 proof 账户并进行验证。
 /// 错误条件：
 pub fn load_proof(
@@ -389,6 +437,174 @@ pub fn load_any_proof(info: &AccountInfo<'_>, is_writable: bool) -> Result<(), P
     Ok(())
 }
 
+/// 加载 pool 账户并验证其运营者。
+/// 错误条件：
+pub fn load_pool(
+    info: &AccountInfo<'_>,
+    operator: &Pubkey,
+    is_writable: bool,
+) -> Result<(), ProgramError> {
+    // 复用 pool 账户的基本验证
+    load_any_pool(info, is_writable)?;
+
+    // 检查矿池的运营者是否匹配
+    let pool_data = info.data.borrow();
+    let pool = Pool::try_from_bytes(&pool_data)?;
+    if pool.operator.ne(operator) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// 加载任意 pool 账户并进行验证。
+/// 错误条件：
+pub fn load_any_pool(info: &AccountInfo<'_>, is_writable: bool) -> Result<(), ProgramError> {
+    // 检查账户的所有者是否为当前程序
+    if info.owner.ne(&crate::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    // 检查账户数据是否为空
+    if info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    // 检查数据是否可以反序列化为 Pool 账户
+    if info.data.borrow()[0].ne(&(Pool::discriminator() as u8)) {
+        return Err(solana_program::program_error::ProgramError::InvalidAccountData);
+    }
+
+    // 检查是否期望可写，但账户不可写
+    if is_writable && !info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// 加载 member 账户并验证其授权者和所属矿池。
+/// 错误条件：
+pub fn load_member(
+    info: &AccountInfo<'_>,
+    authority: &Pubkey,
+    pool: &Pubkey,
+    is_writable: bool,
+) -> Result<(), ProgramError> {
+    // 复用 member 账户的基本验证
+    load_any_member(info, is_writable)?;
+
+    // 检查成员的授权者和所属矿池是否匹配
+    let member_data = info.data.borrow();
+    let member = Member::try_from_bytes(&member_data)?;
+    if member.authority.ne(authority) || member.pool.ne(pool) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// 加载任意 member 账户并进行验证。
+/// 错误条件：
+pub fn load_any_member(info: &AccountInfo<'_>, is_writable: bool) -> Result<(), ProgramError> {
+    // 检查账户的所有者是否为当前程序
+    if info.owner.ne(&crate::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    // 检查账户数据是否为空
+    if info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    // 检查数据是否可以反序列化为 Member 账户
+    if info.data.borrow()[0].ne(&(Member::discriminator() as u8)) {
+        return Err(solana_program::program_error::ProgramError::InvalidAccountData);
+    }
+
+    // 检查是否期望可写，但账户不可写
+    if is_writable && !info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// 加载 miner set 账户并验证其所属的 proof 账户。
+/// 错误条件：
+pub fn load_miner_set(
+    info: &AccountInfo<'_>,
+    proof: &Pubkey,
+    is_writable: bool,
+) -> Result<(), ProgramError> {
+    // 检查账户的所有者是否为当前程序
+    if info.owner.ne(&crate::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    // 检查账户数据是否为空
+    if info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    // 检查数据是否可以反序列化为 MinerSet 账户
+    if info.data.borrow()[0].ne(&(MinerSet::discriminator() as u8)) {
+        return Err(solana_program::program_error::ProgramError::InvalidAccountData);
+    }
+
+    // 检查 miner set 所属的 proof 账户是否匹配
+    let miner_set_data = info.data.borrow();
+    let miner_set = MinerSet::try_from_bytes(&miner_set_data)?;
+    if miner_set.proof.ne(proof) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // 检查是否期望可写，但账户不可写
+    if is_writable && !info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// 加载 session 账户并验证其所属的 proof 账户和会话密钥。
+/// 错误条件：
+pub fn load_session(
+    info: &AccountInfo<'_>,
+    proof: &Pubkey,
+    key: &Pubkey,
+    is_writable: bool,
+) -> Result<(), ProgramError> {
+    // 检查账户的所有者是否为当前程序
+    if info.owner.ne(&crate::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    // 检查账户数据是否为空
+    if info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    // 检查数据是否可以反序列化为 Session 账户
+    if info.data.borrow()[0].ne(&(Session::discriminator() as u8)) {
+        return Err(solana_program::program_error::ProgramError::InvalidAccountData);
+    }
+
+    // 检查会话所属的 proof 账户和会话密钥是否匹配
+    let session_data = info.data.borrow();
+    let session = Session::try_from_bytes(&session_data)?;
+    if session.proof.ne(proof) || session.key.ne(key) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // 检查是否期望可写，但账户不可写
+    if is_writable && !info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
 /// 加载 treasury 账户并进行验证。
 /// 错误条件：
 pub fn load_treasury(info: &AccountInfo<'_>, is_writable: bool) -> Result<(), ProgramError> {
@@ -420,14 +636,59 @@ pub fn load_treasury(info: &AccountInfo<'_>, is_writable: bool) -> Result<(), Pr
     Ok(())
 }
 
+/// 加载事件授权者账户并进行验证。
+/// 错误条件：
+/// - 账户地址不是事件授权者的地址。
+pub fn load_event_authority(info: &AccountInfo<'_>) -> Result<(), ProgramError> {
+    // 检查账户地址是否匹配预期的事件授权者地址
+    if info.key.ne(&EVENT_AUTHORITY_ADDRESS) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(())
+}
+
 /// 加载 treasury 代币账户并进行验证。
 /// 错误条件：
 pub fn load_treasury_tokens(info: &AccountInfo<'_>, is_writable: bool) -> Result<(), ProgramError> {
-    // 检查账户地址是否匹配预期的 treasury 代币地址
-    if info.key.ne(&TREASURY_TOKENS_ADDRESS) {
+    // 检查账户地址是否匹配该代币程序下预期的 treasury 代币地址
+    if info.key.ne(&treasury_tokens_address(info.owner)) {
         return Err(ProgramError::InvalidSeeds);
     }
 
     // 调用 load_token_account 函数验证代币账户
     load_token_account(info, Some(&TREASURY_ADDRESS), &MINT_ADDRESS, is_writable)
+}
+
+/// 加载手续费金库代币账户，检查其地址和授权者。
+pub fn load_fee_vault_tokens(info: &AccountInfo<'_>, is_writable: bool) -> Result<(), ProgramError> {
+    // 检查账户地址是否匹配该代币程序下预期的手续费金库代币地址
+    if !is_fee_vault_tokens(info) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // 调用 load_token_account 函数验证代币账户
+    load_token_account(info, Some(&FEE_VAULT_ADDRESS), &MINT_ADDRESS, is_writable)
+}
+
+/// 在可选账户中查找并加载可写的手续费金库代币账户。
+///
+/// 手续费为 0 时不使用手续费金库，返回 `None` 且不检查账户；否则手续费金库代币账户必须存在。
+pub fn load_optional_fee_vault_tokens<'a, 'info>(
+    infos: &'a [AccountInfo<'info>],
+    fee: u64,
+) -> Result<Option<&'a AccountInfo<'info>>, ProgramError> {
+    if fee.eq(&0) {
+        return Ok(None);
+    }
+    let Some(info) = infos.iter().find(|info| is_fee_vault_tokens(info)) else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_fee_vault_tokens(info, true)?;
+    Ok(Some(info))
+}
+
+/// 账户地址是否为其所属代币程序下的手续费金库代币地址。
+pub fn is_fee_vault_tokens(info: &AccountInfo<'_>) -> bool {
+    info.key.eq(&fee_vault_tokens_address(info.owner))
 }
\ No newline at end of file
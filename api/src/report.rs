@@ -0,0 +1,108 @@
+use bytemuck::{Pod, Zeroable};  // 引入 bytemuck 库以支持零拷贝数据结构
+
+use crate::consts::MAX_BUS_COUNT;
+
+/// SolvencyReport 是审计指令通过返回数据输出的偿付能力报告。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct SolvencyReport {
+    /// 对所有 proof 账户的未偿负债，包括尚未结算的部分。
+    pub liabilities: u64,
+
+    /// 负债中仍累积在 bus 账户、尚未结算到财库的挖矿奖励。
+    pub pending_liabilities: u64,
+
+    /// 财库代币账户的余额。
+    pub balance: u64,
+
+    /// 余额超出负债的部分，包括 bus 中尚未支付的奖励和奖励池。
+    pub surplus: u64,
+
+    /// 负债超出余额的部分。不为 0 时表示财库资不抵债。
+    pub deficit: u64,
+}
+
+impl SolvencyReport {
+    /// 根据负债和余额生成报告。
+    pub fn new(liabilities: u64, pending_liabilities: u64, balance: u64) -> Self {
+        Self {
+            liabilities,
+            pending_liabilities,
+            balance,
+            surplus: balance.saturating_sub(liabilities),
+            deficit: liabilities.saturating_sub(balance),
+        }
+    }
+
+    /// 财库余额是否足以覆盖所有负债。
+    pub fn is_solvent(&self) -> bool {
+        self.deficit.eq(&0)
+    }
+
+    /// 将报告序列化为返回数据。
+    pub fn to_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+
+    /// 从审计指令的返回数据中解析报告。
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        bytemuck::try_pod_read_unaligned(data).ok()
+    }
+}
+
+/// HealthReport 是健康检查指令通过返回数据输出的协议状态，矿工可以模拟该指令来决定下一步操作。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct HealthReport {
+    /// 距离下一次可以重置的秒数。重置已到期时为 0。
+    pub seconds_until_reset: u64,
+
+    /// 重置是否已到期但尚未执行，1 表示是，0 表示否。
+    pub reset_overdue: u64,
+
+    /// 已创建的 bus 账户数量。
+    pub bus_count: u64,
+
+    /// 每个 bus 在当前周期内剩余的奖励。只有前 `bus_count` 项有效。
+    pub bus_rewards: [u64; MAX_BUS_COUNT],
+
+    /// 当前的最低难度。
+    pub min_difficulty: u64,
+
+    /// 当前的基础奖励率。
+    pub base_reward_rate: u64,
+
+    /// 代币的当前供应量。
+    pub supply: u64,
+
+    /// 代币的最大供应量。
+    pub max_supply: u64,
+
+    /// 距离调用者的 proof 账户可以提交下一个哈希而不被视为垃圾请求的秒数。
+    pub seconds_until_submission: u64,
+}
+
+impl HealthReport {
+    /// 重置是否已到期但尚未执行。
+    pub fn is_reset_overdue(&self) -> bool {
+        self.reset_overdue.ne(&0)
+    }
+
+    /// 当前周期内所有 bus 剩余的奖励总额。
+    pub fn total_bus_rewards(&self) -> u64 {
+        self.bus_rewards
+            .iter()
+            .take(self.bus_count as usize)
+            .fold(0u64, |acc, rewards| acc.saturating_add(*rewards))
+    }
+
+    /// 将报告序列化为返回数据。
+    pub fn to_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+
+    /// 从健康检查指令的返回数据中解析报告。
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        bytemuck::try_pod_read_unaligned(data).ok()
+    }
+}
@@ -0,0 +1,240 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::{
+    consts::{BPS_DENOMINATOR, LOCK_MULTIPLIERS, ONE_MINUTE},
+    state::{Bus, Config, Proof},
+};
+
+/// 一个哈希的奖励。报价指令通过返回数据输出该结构体。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Pod, Zeroable)]
+pub struct Reward {
+    /// 不考虑 bus 剩余奖励和单个哈希奖励上限时应支付的奖励，用于计算新的奖励率。
+    pub theoretical: u64,
+
+    /// 实际支付给 proof 账户的奖励，不含幸运奖池。
+    pub actual: u64,
+}
+
+impl Reward {
+    /// 将奖励序列化为返回数据。
+    pub fn to_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+
+    /// 从报价指令的返回数据中解析奖励。
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        bytemuck::try_pod_read_unaligned(data).ok()
+    }
+}
+
+/// 计算在时间 `t` 提交难度为 `difficulty` 的哈希可以获得的奖励。
+///
+/// 挖矿指令和报价指令共享此函数，客户端也可以在链下使用它来预测奖励。该函数不检查解决方案的有效性、
+/// 垃圾请求或周期是否需要重置。难度低于最低难度时奖励为 0。
+///
+/// 报价指令允许任何人传入任意难度，因此所有计算都是饱和的，溢出时奖励取 `u64::MAX`，
+/// 再由 bus 剩余奖励和单个哈希奖励上限约束实际支付金额。
+pub fn calculate_reward(
+    config: &Config,
+    proof: &Proof,
+    bus: &Bus,
+    difficulty: u32,
+    t: i64,
+) -> Reward {
+    // 规范化难度并计算奖励金额
+    let Some(normalized_difficulty) = difficulty.checked_sub(config.min_difficulty as u32) else {
+        return Reward {
+            theoretical: 0,
+            actual: 0,
+        };
+    };
+    let mut reward = config
+        .base_reward_rate
+        .saturating_mul(2u64.saturating_pow(normalized_difficulty));
+
+    // 应用质押乘数，锁定中的质押按档位乘数放大
+    if proof.stake.gt(&0)
+        && proof.last_stake_at.saturating_add(ONE_MINUTE).lt(&t)
+        && config.top_balance.gt(&0)
+    {
+        let lock_tier = if proof.lock_until.gt(&t) {
+            proof.lock_tier as usize
+        } else {
+            0
+        };
+        let multiplier = LOCK_MULTIPLIERS
+            .get(lock_tier)
+            .copied()
+            .unwrap_or(LOCK_MULTIPLIERS[0]);
+        let staking_reward = (reward as u128)
+            .saturating_mul(proof.stake.min(config.top_balance) as u128)
+            .saturating_mul(multiplier as u128)
+            .saturating_div(config.top_balance as u128)
+            .saturating_div(BPS_DENOMINATOR as u128)
+            .min(u64::MAX as u128) as u64;
+        reward = reward.saturating_add(staking_reward);
+    }
+
+    // 应用活跃度惩罚
+    //
+    // The liveness penalty exists to ensure there is no "invisible" hashpower on the network. It
+    // should not be possible to spend ~1 hour on a given challenge and submit a hash with a large
+    // difficulty value to earn an outsized reward.
+    //
+    // The penalty works by halving the reward amount for every minute late the solution has been submitted.
+    // This ultimately drives the reward to zero given enough time (10-20 minutes).
+    let t_target = proof.last_hash_at.saturating_add(ONE_MINUTE);
+    let t_liveness = t_target.saturating_add(config.tolerance);
+    if t.gt(&t_liveness) {
+        // 每分钟迟交奖励减半
+        let tardiness = t.saturating_sub(t_target) as u64;
+        let halvings = tardiness.saturating_div(ONE_MINUTE as u64);
+        if halvings.gt(&0) {
+            let halvings = u32::try_from(halvings).unwrap_or(u32::MAX);
+            reward = reward.saturating_div(2u64.saturating_pow(halvings));
+        }
+
+        // 余秒线性衰减
+        let remainder_secs = tardiness.saturating_sub(halvings.saturating_mul(ONE_MINUTE as u64));
+        if remainder_secs.gt(&0) && reward.gt(&0) {
+            let penalty = reward
+                .saturating_div(2)
+                .saturating_mul(remainder_secs)
+                .saturating_div(ONE_MINUTE as u64);
+            reward = reward.saturating_sub(penalty);
+        }
+    }
+
+    // 限制支付金额到总线剩余部分
+    Reward {
+        theoretical: reward,
+        actual: reward.min(bus.rewards).min(config.max_hash_reward),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::Zeroable;
+
+    use super::calculate_reward;
+    use crate::{
+        consts::{BUS_EPOCH_REWARDS, MAX_HASH_REWARD, ONE_MINUTE, TOLERANCE},
+        state::{Bus, Config, Proof},
+    };
+
+    fn setup() -> (Config, Proof, Bus) {
+        let mut config = Config::zeroed();
+        config.base_reward_rate = 1_000;
+        config.min_difficulty = 10;
+        config.tolerance = TOLERANCE;
+        config.max_hash_reward = MAX_HASH_REWARD;
+        let proof = Proof::zeroed();
+        let mut bus = Bus::zeroed();
+        bus.rewards = BUS_EPOCH_REWARDS;
+        (config, proof, bus)
+    }
+
+    #[test]
+    fn test_calculate_reward_doubles_per_difficulty() {
+        let (config, proof, bus) = setup();
+        let t = ONE_MINUTE;
+        assert_eq!(calculate_reward(&config, &proof, &bus, 10, t).theoretical, 1_000);
+        assert_eq!(calculate_reward(&config, &proof, &bus, 13, t).theoretical, 8_000);
+    }
+
+    #[test]
+    fn test_calculate_reward_too_easy() {
+        let (config, proof, bus) = setup();
+        let reward = calculate_reward(&config, &proof, &bus, 9, ONE_MINUTE);
+        assert_eq!(reward.theoretical, 0);
+        assert_eq!(reward.actual, 0);
+    }
+
+    #[test]
+    fn test_calculate_reward_staking_multiplier() {
+        let (mut config, mut proof, bus) = setup();
+        config.top_balance = 100;
+        proof.stake = 50;
+        let t = ONE_MINUTE * 2;
+        proof.last_hash_at = t - ONE_MINUTE;
+        assert_eq!(calculate_reward(&config, &proof, &bus, 10, t).theoretical, 1_500);
+    }
+
+    #[test]
+    fn test_calculate_reward_lock_tier() {
+        let (mut config, mut proof, bus) = setup();
+        config.top_balance = 100;
+        proof.stake = 50;
+        let t = ONE_MINUTE * 2;
+        proof.last_hash_at = t - ONE_MINUTE;
+
+        // 锁定中的质押按档位乘数放大
+        proof.lock_tier = 3;
+        proof.lock_until = t + 1;
+        assert_eq!(calculate_reward(&config, &proof, &bus, 10, t).theoretical, 2_500);
+
+        // 锁定到期后回到档位 0
+        proof.lock_until = t;
+        assert_eq!(calculate_reward(&config, &proof, &bus, 10, t).theoretical, 1_500);
+    }
+
+    #[test]
+    fn test_calculate_reward_liveness_penalty() {
+        let (config, proof, bus) = setup();
+        let t = ONE_MINUTE * 3;
+        assert_eq!(calculate_reward(&config, &proof, &bus, 10, t).theoretical, 250);
+    }
+
+    #[test]
+    fn test_calculate_reward_huge_difficulty() {
+        // 报价指令可以传入任意难度，奖励饱和而不会溢出
+        let (config, proof, bus) = setup();
+        let reward = calculate_reward(&config, &proof, &bus, u32::MAX, ONE_MINUTE);
+        assert_eq!(reward.theoretical, u64::MAX);
+        assert_eq!(reward.actual, MAX_HASH_REWARD.min(BUS_EPOCH_REWARDS));
+    }
+
+    #[test]
+    fn test_calculate_reward_huge_stake() {
+        let (mut config, mut proof, bus) = setup();
+        config.base_reward_rate = u64::MAX;
+        config.top_balance = u64::MAX;
+        proof.stake = u64::MAX;
+        proof.lock_tier = 3;
+        let t = ONE_MINUTE * 2;
+        proof.last_hash_at = t - ONE_MINUTE;
+        proof.lock_until = t + 1;
+        let reward = calculate_reward(&config, &proof, &bus, 64, t);
+        assert_eq!(reward.theoretical, u64::MAX);
+    }
+
+    #[test]
+    fn test_calculate_reward_invalid_lock_tier() {
+        // 越界的锁定档位按档位 0 计算
+        let (mut config, mut proof, bus) = setup();
+        config.top_balance = 100;
+        proof.stake = 50;
+        let t = ONE_MINUTE * 2;
+        proof.last_hash_at = t - ONE_MINUTE;
+        proof.lock_tier = u64::MAX;
+        proof.lock_until = t + 1;
+        assert_eq!(calculate_reward(&config, &proof, &bus, 10, t).theoretical, 1_500);
+    }
+
+    #[test]
+    fn test_calculate_reward_extremely_late() {
+        let (config, proof, bus) = setup();
+        let reward = calculate_reward(&config, &proof, &bus, 10, i64::MAX);
+        assert_eq!(reward.theoretical, 0);
+    }
+
+    #[test]
+    fn test_calculate_reward_capped_by_bus() {
+        let (config, proof, mut bus) = setup();
+        bus.rewards = 300;
+        let reward = calculate_reward(&config, &proof, &bus, 10, ONE_MINUTE);
+        assert_eq!(reward.theoretical, 1_000);
+        assert_eq!(reward.actual, 300);
+    }
+}
@@ -1,5 +1,6 @@
 use drillx::Solution;
 use solana_program::{
+    ed25519_program,
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     system_program, sysvar,
@@ -8,7 +9,10 @@ use solana_program::{
 use crate::{
     consts::*,
     instruction::*,
-    state::{bus_pda, config_pda, proof_pda, treasury_pda},
+    state::{
+        bus_pda, config_pda, member_pda, miner_set_pda, pool_pda, proof_pda, session_pda,
+        treasury_pda, Config,
+    },
 };
 
 /// Builds an auth instruction.
@@ -20,22 +24,94 @@ pub fn auth(proof: Pubkey) -> Instruction {
     }
 }
 
-/// Builds a claim instruction.
-pub fn claim(signer: Pubkey, beneficiary: Pubkey, amount: u64) -> Instruction {
+/// Builds an auth instruction covering several proofs, for use with a mine batch instruction.
+pub fn auth_batch(proofs: &[Pubkey]) -> Instruction {
+    Instruction {
+        program_id: NOOP_PROGRAM_ID,
+        accounts: vec![],
+        data: proofs.iter().flat_map(|proof| proof.to_bytes()).collect(),
+    }
+}
+
+/// Returns the message the proof authority must sign in the ed25519 auth mode.
+pub fn auth_message(proof: Pubkey, challenge: [u8; 32]) -> [u8; 64] {
+    let mut message = [0u8; 64];
+    message[..32].copy_from_slice(proof.as_ref());
+    message[32..].copy_from_slice(&challenge);
+    message
+}
+
+/// Builds an ed25519 precompile instruction carrying the authority's signature over the auth message.
+pub fn auth_ed25519(
+    authority: Pubkey,
+    proof: Pubkey,
+    challenge: [u8; 32],
+    signature: [u8; 64],
+) -> Instruction {
+    // Layout: signature count, padding, offsets, then the public key, signature and message.
+    let public_key_offset: u16 = 16;
+    let signature_offset: u16 = public_key_offset + 32;
+    let message_offset: u16 = signature_offset + 64;
+    let mut data = vec![1u8, 0];
+    for value in [
+        signature_offset,
+        u16::MAX,
+        public_key_offset,
+        u16::MAX,
+        message_offset,
+        64,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(authority.as_ref());
+    data.extend_from_slice(&signature);
+    data.extend_from_slice(&auth_message(proof, challenge));
+    Instruction {
+        program_id: ed25519_program::id(),
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Builds the auth instruction for the configured auth mode, or none if the mode does not need one.
+/// The signer is only called in the ed25519 mode, with the message returned by `auth_message`.
+pub fn auth_for_mode(
+    auth_mode: u64,
+    authority: Pubkey,
+    proof: Pubkey,
+    challenge: [u8; 32],
+    sign: impl FnOnce(&[u8]) -> [u8; 64],
+) -> Option<Instruction> {
+    match auth_mode {
+        AUTH_MODE_NOOP => Some(auth(proof)),
+        AUTH_MODE_ED25519 => {
+            let signature = sign(&auth_message(proof, challenge));
+            Some(auth_ed25519(authority, proof, challenge, signature))
+        }
+        _ => None,
+    }
+}
+
+/// Builds a claim instruction. The fee vault token account is a trailing optional account and only
+/// needs to exist while `Config.claim_fee_bps` is nonzero.
+pub fn claim(signer: Pubkey, beneficiary: Pubkey, amount: u64, token_program: Pubkey) -> Instruction {
     let proof = proof_pda(signer).0;
-    let treasury_tokens = spl_associated_token_account::get_associated_token_address(
-        &TREASURY_ADDRESS,
-        &MINT_ADDRESS,
-    );
+    let treasury_tokens = treasury_tokens_address(&token_program);
     Instruction {
         program_id: crate::id(),
         accounts: vec![
             AccountMeta::new(signer, true),
             AccountMeta::new(beneficiary, false),
             AccountMeta::new(proof, false),
-            AccountMeta::new_readonly(TREASURY_ADDRESS, false),
+            AccountMeta::new(TREASURY_ADDRESS, false),
             AccountMeta::new(treasury_tokens, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(CONFIG_ADDRESS, false),
+            AccountMeta::new_readonly(EVENT_AUTHORITY_ADDRESS, false),
+            AccountMeta::new_readonly(crate::id(), false),
+            AccountMeta::new_readonly(MINT_ADDRESS, false),
+            AccountMeta::new(fee_vault_tokens_address(&token_program), false),
         ],
         data: Claim {
             amount: amount.to_le_bytes(),
@@ -44,56 +120,124 @@ pub fn claim(signer: Pubkey, beneficiary: Pubkey, amount: u64) -> Instruction {
     }
 }
 
-/// Builds a health instruction.
-pub fn health(signer: Pubkey) -> Instruction {
+/// Builds a claim instruction signed by a session key on behalf of the proof authority.
+pub fn claim_with_session(
+    signer: Pubkey,
+    authority: Pubkey,
+    beneficiary: Pubkey,
+    amount: u64,
+    token_program: Pubkey,
+) -> Instruction {
+    let proof = proof_pda(authority).0;
+    let mut ix = claim(authority, beneficiary, amount, token_program);
+    ix.accounts[0] = AccountMeta::new(signer, true);
+    ix.accounts.push(AccountMeta::new(session_pda(proof, signer).0, false));
+    ix
+}
+
+/// Builds a health instruction. The health report is returned as return data and can be parsed
+/// with `HealthReport::from_bytes`. The bus count must match `Config.bus_count`.
+pub fn health(signer: Pubkey, bus_count: u64) -> Instruction {
     let proof = proof_pda(signer).0;
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new_readonly(CONFIG_ADDRESS, false),
+        AccountMeta::new_readonly(MINT_ADDRESS, false),
+        AccountMeta::new_readonly(proof, false),
+    ];
+    accounts.extend(
+        BUS_ADDRESSES
+            .iter()
+            .take(bus_count as usize)
+            .map(|bus| AccountMeta::new_readonly(*bus, false)),
+    );
     Instruction {
         program_id: crate::id(),
-        accounts: vec![
-            AccountMeta::new(signer, true),
-            AccountMeta::new(proof, false),
-        ],
+        accounts,
         data: Health {}.to_bytes(),
     }
 }
 
-/// Builds a close instruction.
-pub fn close(signer: Pubkey) -> Instruction {
+/// Builds a close instruction. `linked_accounts` lists the miner set and sessions of the proof, which
+/// are closed along with it.
+pub fn close(signer: Pubkey, linked_accounts: &[Pubkey]) -> Instruction {
     let proof = proof_pda(signer).0;
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(proof, false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(EVENT_AUTHORITY_ADDRESS, false),
+        AccountMeta::new_readonly(crate::id(), false),
+    ];
+    accounts.extend(linked_accounts.iter().map(|key| AccountMeta::new(*key, false)));
     Instruction {
         program_id: crate::id(),
-        accounts: vec![
-            AccountMeta::new(signer, true),
-            AccountMeta::new(proof, false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        ],
+        accounts,
         data: Close {}.to_bytes(),
     }
 }
 
+/// Builds a reap instruction. The signer receives a tip out of the reclaimed rent and the rest is
+/// returned to the proof authority. `linked_accounts` lists the miner set and sessions of the proof.
+pub fn reap(signer: Pubkey, authority: Pubkey, linked_accounts: &[Pubkey]) -> Instruction {
+    let proof = proof_pda(authority).0;
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(authority, false),
+        AccountMeta::new_readonly(CONFIG_ADDRESS, false),
+        AccountMeta::new(proof, false),
+        AccountMeta::new_readonly(EVENT_AUTHORITY_ADDRESS, false),
+        AccountMeta::new_readonly(crate::id(), false),
+    ];
+    accounts.extend(linked_accounts.iter().map(|key| AccountMeta::new(*key, false)));
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: Reap {}.to_bytes(),
+    }
+}
+
 /// 构建一个挖矿指令
 pub fn mine(signer: Pubkey, authority: Pubkey, bus: Pubkey, solution: Solution) -> Instruction {
+    mine_with_fallback(signer, authority, bus, &[], solution)
+}
+
+/// 构建带有备用 bus 的挖矿指令。如果 `bus` 已耗尽，程序会从第一个仍有奖励的备用 bus 中支付。
+pub fn mine_with_fallback(
+    signer: Pubkey,
+    authority: Pubkey,
+    bus: Pubkey,
+    fallback_busses: &[Pubkey],
+    solution: Solution,
+) -> Instruction {
     // 获取与authority相关的proof PDA(程序派生地址)
     let proof = proof_pda(authority).0;
+    // 指定此指令所需的账户列表
+    let mut accounts = vec![
+        // 签名者账户，必须提供签名
+        AccountMeta::new(signer, true),
+        // bus账户，不需要提供签名
+        AccountMeta::new(bus, false),
+        // 配置地址账户，只读，不需要提供签名
+        AccountMeta::new_readonly(CONFIG_ADDRESS, false),
+        // proof PDA 账户，不需要提供签名
+        AccountMeta::new(proof, false),
+        // 系统变量账户，用于读取指令信息，只读，不需要提供签名
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        // 系统变量账户，用于读取槽哈希信息，只读，不需要提供签名
+        AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+        // 事件授权者账户，用于记录事件，只读，不需要提供签名
+        AccountMeta::new_readonly(EVENT_AUTHORITY_ADDRESS, false),
+        // 本程序账户，用于通过自调用记录事件，只读，不需要提供签名
+        AccountMeta::new_readonly(crate::id(), false),
+    ];
+    // 备用 bus 账户，不需要提供签名
+    accounts.extend(fallback_busses.iter().map(|bus| AccountMeta::new(*bus, false)));
     // 创建并返回一个新的指令
     Instruction {
         // 指定要调用的智能合约的程序ID
         program_id: crate::id(),
-        // 指定此指令所需的账户列表
-        accounts: vec![
-            // 签名者账户，必须提供签名
-            AccountMeta::new(signer, true),
-            // bus账户，不需要提供签名
-            AccountMeta::new(bus, false),
-            // 配置地址账户，只读，不需要提供签名
-            AccountMeta::new_readonly(CONFIG_ADDRESS, false),
-            // proof PDA 账户，不需要提供签名
-            AccountMeta::new(proof, false),
-            // 系统变量账户，用于读取指令信息，只读，不需要提供签名
-            AccountMeta::new_readonly(sysvar::instructions::id(), false),
-            // 系统变量账户，用于读取槽哈希信息，只读，不需要提供签名
-            AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
-        ],
+        accounts,
         // 将解决方案中的 digest 和 nonce 转换为字节数组
         data: Mine {
             digest: solution.d,
@@ -102,6 +246,162 @@ pub fn mine(signer: Pubkey, authority: Pubkey, bus: Pubkey, solution: Solution)
     }
 }
 
+/// 构建批量挖矿指令。`solutions` 中的每一项为 proof 授权者及其解决方案，签名者必须是所有 proof 的矿工。
+pub fn mine_batch(signer: Pubkey, bus: Pubkey, solutions: &[(Pubkey, Solution)]) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(bus, false),
+        AccountMeta::new_readonly(CONFIG_ADDRESS, false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+        AccountMeta::new_readonly(EVENT_AUTHORITY_ADDRESS, false),
+        AccountMeta::new_readonly(crate::id(), false),
+    ];
+    // proof 账户，与解决方案一一对应
+    accounts.extend(
+        solutions
+            .iter()
+            .map(|(authority, _)| AccountMeta::new(proof_pda(*authority).0, false)),
+    );
+    // 指令数据之后依次跟随每个解决方案
+    let mut data = MineBatch {}.to_bytes();
+    for (_, solution) in solutions {
+        data.extend_from_slice(bytemuck::bytes_of(&Mine {
+            digest: solution.d,
+            nonce: solution.n,
+        }));
+    }
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data,
+    }
+}
+
+/// 构建由 miner set 中的授权矿工签名的挖矿指令。
+pub fn mine_with_miner_set(
+    signer: Pubkey,
+    authority: Pubkey,
+    bus: Pubkey,
+    fallback_busses: &[Pubkey],
+    solution: Solution,
+) -> Instruction {
+    let miner_set = miner_set_pda(proof_pda(authority).0).0;
+    let mut ix = mine_with_fallback(signer, authority, bus, fallback_busses, solution);
+    // miner set 账户，只读，不需要提供签名
+    ix.accounts.push(AccountMeta::new_readonly(miner_set, false));
+    ix
+}
+
+/// 构建由会话密钥签名的挖矿指令。
+pub fn mine_with_session(
+    signer: Pubkey,
+    authority: Pubkey,
+    bus: Pubkey,
+    fallback_busses: &[Pubkey],
+    solution: Solution,
+) -> Instruction {
+    let session = session_pda(proof_pda(authority).0, signer).0;
+    let mut ix = mine_with_fallback(signer, authority, bus, fallback_busses, solution);
+    // session 账户，只读，不需要提供签名
+    ix.accounts.push(AccountMeta::new_readonly(session, false));
+    ix
+}
+
+/// 构建开启会话指令。`claim_limit` 为 0 时会话密钥只能挖矿，不能领取奖励。
+pub fn open_session(
+    signer: Pubkey,
+    key: Pubkey,
+    beneficiary: Pubkey,
+    expires_at: i64,
+    claim_limit: u64,
+) -> Instruction {
+    let proof = proof_pda(signer).0;
+    let session_pda = session_pda(proof, key);
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(key, false),
+            AccountMeta::new_readonly(beneficiary, false),
+            AccountMeta::new(session_pda.0, false),
+            AccountMeta::new(proof, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: OpenSession {
+            bump: session_pda.1,
+            expires_at: expires_at.to_le_bytes(),
+            claim_limit: claim_limit.to_le_bytes(),
+        }
+            .to_bytes(),
+    }
+}
+
+/// 构建关闭会话指令。
+pub fn close_session(signer: Pubkey, key: Pubkey) -> Instruction {
+    let proof = proof_pda(signer).0;
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(key, false),
+            AccountMeta::new(session_pda(proof, key).0, false),
+            AccountMeta::new(proof, false),
+        ],
+        data: CloseSession {}.to_bytes(),
+    }
+}
+
+/// 构建授权矿工密钥指令。`expires_at` 为密钥的过期插槽，为 0 时永不过期。
+pub fn add_miner(signer: Pubkey, miner: Pubkey, expires_at: u64) -> Instruction {
+    let proof = proof_pda(signer).0;
+    let miner_set_pda = miner_set_pda(proof);
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(miner, false),
+            AccountMeta::new(miner_set_pda.0, false),
+            AccountMeta::new(proof, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: AddMiner {
+            bump: miner_set_pda.1,
+            expires_at: expires_at.to_le_bytes(),
+        }
+            .to_bytes(),
+    }
+}
+
+/// 构建关闭 miner set 指令，租金退还给签名者。
+pub fn close_miner_set(signer: Pubkey) -> Instruction {
+    let proof = proof_pda(signer).0;
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(miner_set_pda(proof).0, false),
+            AccountMeta::new(proof, false),
+        ],
+        data: CloseMinerSet {}.to_bytes(),
+    }
+}
+
+/// 构建移除矿工密钥指令。
+pub fn remove_miner(signer: Pubkey, miner: Pubkey) -> Instruction {
+    let proof = proof_pda(signer).0;
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(miner, false),
+            AccountMeta::new(miner_set_pda(proof).0, false),
+            AccountMeta::new_readonly(proof, false),
+        ],
+        data: RemoveMiner {}.to_bytes(),
+    }
+}
+
 /// 构建一个打开指令。
 pub fn open(signer: Pubkey, miner: Pubkey, payer: Pubkey) -> Instruction {
     // 获取与 signer 相关的 proof PDA（程序派生地址）
@@ -126,47 +426,108 @@ pub fn open(signer: Pubkey, miner: Pubkey, payer: Pubkey) -> Instruction {
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             // 系统变量账户，用于读取槽哈希信息，只读，不需要提供签名
             AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+            AccountMeta::new_readonly(EVENT_AUTHORITY_ADDRESS, false),
+            AccountMeta::new_readonly(crate::id(), false),
         ],
         // 将 proof PDA 的 bump 值转换为字节数组
         data: Open { bump: proof_pda.1 }.to_bytes(),
     }
 }
 
-/// Builds a reset instruction.
-pub fn reset(signer: Pubkey) -> Instruction {
-    let treasury_tokens = spl_associated_token_account::get_associated_token_address(
-        &TREASURY_ADDRESS,
-        &MINT_ADDRESS,
+/// Builds a rebalance instruction. The bus count must match `Config.bus_count`.
+pub fn rebalance(signer: Pubkey, bus_count: u64) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new_readonly(CONFIG_ADDRESS, false),
+    ];
+    accounts.extend(
+        BUS_ADDRESSES
+            .iter()
+            .take(bus_count as usize)
+            .map(|bus| AccountMeta::new(*bus, false)),
     );
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: Rebalance {}.to_bytes(),
+    }
+}
+
+/// Builds an audit instruction. The solvency report is returned as return data and can be parsed
+/// with `SolvencyReport::from_bytes`.
+pub fn audit(bus_count: u64, token_program: Pubkey) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(CONFIG_ADDRESS, false),
+        AccountMeta::new_readonly(TREASURY_ADDRESS, false),
+        AccountMeta::new_readonly(treasury_tokens_address(&token_program), false),
+    ];
+    accounts.extend(
+        BUS_ADDRESSES
+            .iter()
+            .take(bus_count as usize)
+            .map(|bus| AccountMeta::new_readonly(*bus, false)),
+    );
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: Audit {}.to_bytes(),
+    }
+}
+
+/// Builds a quote instruction. The expected reward is returned as return data and can be parsed
+/// with `Reward::from_bytes`.
+pub fn quote(authority: Pubkey, bus: Pubkey, difficulty: u32) -> Instruction {
     Instruction {
         program_id: crate::id(),
         accounts: vec![
-            AccountMeta::new(signer, true),
-            AccountMeta::new(BUS_ADDRESSES[0], false),
-            AccountMeta::new(BUS_ADDRESSES[1], false),
-            AccountMeta::new(BUS_ADDRESSES[2], false),
-            AccountMeta::new(BUS_ADDRESSES[3], false),
-            AccountMeta::new(BUS_ADDRESSES[4], false),
-            AccountMeta::new(BUS_ADDRESSES[5], false),
-            AccountMeta::new(BUS_ADDRESSES[6], false),
-            AccountMeta::new(BUS_ADDRESSES[7], false),
-            AccountMeta::new(CONFIG_ADDRESS, false),
-            AccountMeta::new(MINT_ADDRESS, false),
-            AccountMeta::new(TREASURY_ADDRESS, false),
-            AccountMeta::new(treasury_tokens, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(CONFIG_ADDRESS, false),
+            AccountMeta::new_readonly(proof_pda(authority).0, false),
+            AccountMeta::new_readonly(bus, false),
         ],
+        data: Quote {
+            difficulty: (difficulty as u64).to_le_bytes(),
+        }
+            .to_bytes(),
+    }
+}
+
+/// Builds a reset instruction. The bus count must match `Config.bus_count` and the beneficiary token
+/// account must be owned by the signer.
+pub fn reset(
+    signer: Pubkey,
+    beneficiary: Pubkey,
+    bus_count: u64,
+    token_program: Pubkey,
+) -> Instruction {
+    let treasury_tokens = treasury_tokens_address(&token_program);
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(beneficiary, false),
+        AccountMeta::new(CONFIG_ADDRESS, false),
+        AccountMeta::new(MINT_ADDRESS, false),
+        AccountMeta::new(TREASURY_ADDRESS, false),
+        AccountMeta::new(treasury_tokens, false),
+        AccountMeta::new_readonly(token_program, false),
+        AccountMeta::new_readonly(EVENT_AUTHORITY_ADDRESS, false),
+        AccountMeta::new_readonly(crate::id(), false),
+    ];
+    accounts.extend(
+        BUS_ADDRESSES
+            .iter()
+            .take(bus_count as usize)
+            .map(|bus| AccountMeta::new(*bus, false)),
+    );
+    Instruction {
+        program_id: crate::id(),
+        accounts,
         data: Reset {}.to_bytes(),
     }
 }
 
 /// Build a stake instruction.
-pub fn stake(signer: Pubkey, sender: Pubkey, amount: u64) -> Instruction {
+pub fn stake(signer: Pubkey, sender: Pubkey, amount: u64, token_program: Pubkey) -> Instruction {
     let proof = proof_pda(signer).0;
-    let treasury_tokens = spl_associated_token_account::get_associated_token_address(
-        &TREASURY_ADDRESS,
-        &MINT_ADDRESS,
-    );
+    let treasury_tokens = treasury_tokens_address(&token_program);
     Instruction {
         program_id: crate::id(),
         accounts: vec![
@@ -174,7 +535,12 @@ pub fn stake(signer: Pubkey, sender: Pubkey, amount: u64) -> Instruction {
             AccountMeta::new(proof, false),
             AccountMeta::new(sender, false),
             AccountMeta::new(treasury_tokens, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(CONFIG_ADDRESS, false),
+            AccountMeta::new_readonly(EVENT_AUTHORITY_ADDRESS, false),
+            AccountMeta::new_readonly(crate::id(), false),
+            AccountMeta::new_readonly(MINT_ADDRESS, false),
+            AccountMeta::new(TREASURY_ADDRESS, false),
         ],
         data: Stake {
             amount: amount.to_le_bytes(),
@@ -183,6 +549,251 @@ pub fn stake(signer: Pubkey, sender: Pubkey, amount: u64) -> Instruction {
     }
 }
 
+/// Builds an unstake instruction.
+pub fn unstake(
+    signer: Pubkey,
+    beneficiary: Pubkey,
+    amount: u64,
+    token_program: Pubkey,
+) -> Instruction {
+    let proof = proof_pda(signer).0;
+    let treasury_tokens = treasury_tokens_address(&token_program);
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(beneficiary, false),
+            AccountMeta::new_readonly(CONFIG_ADDRESS, false),
+            AccountMeta::new(proof, false),
+            AccountMeta::new(TREASURY_ADDRESS, false),
+            AccountMeta::new(treasury_tokens, false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(EVENT_AUTHORITY_ADDRESS, false),
+            AccountMeta::new_readonly(crate::id(), false),
+            AccountMeta::new_readonly(MINT_ADDRESS, false),
+        ],
+        data: Unstake {
+            amount: amount.to_le_bytes(),
+        }
+            .to_bytes(),
+    }
+}
+
+/// Builds a migrate instruction to resize a proof or bus account to the current layout.
+pub fn migrate(signer: Pubkey, account: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(account, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: Migrate {}.to_bytes(),
+    }
+}
+
+/// Builds a lock instruction.
+pub fn lock(signer: Pubkey, tier: u8) -> Instruction {
+    let proof = proof_pda(signer).0;
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(proof, false),
+        ],
+        data: Lock { tier }.to_bytes(),
+    }
+}
+
+/// Builds a propose authority instruction. Proposing `Pubkey::default()` cancels a pending transfer.
+pub fn propose_authority(signer: Pubkey, new_authority: Pubkey) -> Instruction {
+    let proof = proof_pda(signer).0;
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(new_authority, false),
+            AccountMeta::new(proof, false),
+        ],
+        data: ProposeAuthority {}.to_bytes(),
+    }
+}
+
+/// Builds an accept authority instruction. The proof is moved from the old authority's PDA to the signer's.
+/// The old proof's miner set and sessions must be listed in `linked_accounts` and are closed. All rent is
+/// refunded to the old authority.
+pub fn accept_authority(
+    signer: Pubkey,
+    old_authority: Pubkey,
+    linked_accounts: &[Pubkey],
+) -> Instruction {
+    let proof = proof_pda(old_authority).0;
+    let new_proof = proof_pda(signer);
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(old_authority, false),
+        AccountMeta::new(proof, false),
+        AccountMeta::new(new_proof.0, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend(linked_accounts.iter().map(|key| AccountMeta::new(*key, false)));
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: AcceptAuthority { bump: new_proof.1 }.to_bytes(),
+    }
+}
+
+/// Builds a donate instruction.
+pub fn donate(signer: Pubkey, sender: Pubkey, amount: u64, token_program: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(CONFIG_ADDRESS, false),
+            AccountMeta::new(sender, false),
+            AccountMeta::new(treasury_tokens_address(&token_program), false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(MINT_ADDRESS, false),
+            AccountMeta::new_readonly(EVENT_AUTHORITY_ADDRESS, false),
+            AccountMeta::new_readonly(crate::id(), false),
+        ],
+        data: Donate {
+            amount: amount.to_le_bytes(),
+        }
+            .to_bytes(),
+    }
+}
+
+/// Builds an open pool instruction.
+pub fn open_pool(signer: Pubkey) -> Instruction {
+    let pool_pda = pool_pda(signer);
+    let proof_pda = proof_pda(pool_pda.0);
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(pool_pda.0, false),
+            AccountMeta::new(proof_pda.0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+        ],
+        data: OpenPool {
+            pool_bump: pool_pda.1,
+            proof_bump: proof_pda.1,
+        }
+            .to_bytes(),
+    }
+}
+
+/// Builds a join pool instruction.
+pub fn join_pool(signer: Pubkey, pool: Pubkey) -> Instruction {
+    let member_pda = member_pda(pool, signer);
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(pool, false),
+            AccountMeta::new(member_pda.0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: JoinPool { bump: member_pda.1 }.to_bytes(),
+    }
+}
+
+/// Builds a pool submit instruction. The upper 32 bits of the solution nonce must equal the member's
+/// `id`, so each member searches its own nonce range.
+pub fn pool_submit(signer: Pubkey, pool: Pubkey, solution: Solution) -> Instruction {
+    let member = member_pda(pool, signer).0;
+    let proof = proof_pda(pool).0;
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(CONFIG_ADDRESS, false),
+            AccountMeta::new(pool, false),
+            AccountMeta::new(member, false),
+            AccountMeta::new_readonly(proof, false),
+        ],
+        data: PoolSubmit {
+            digest: solution.d,
+            nonce: solution.n,
+        }
+            .to_bytes(),
+    }
+}
+
+/// Builds a pool mine instruction. Members from the previous round must be settled first.
+pub fn pool_mine(signer: Pubkey, bus: Pubkey) -> Instruction {
+    let pool = pool_pda(signer).0;
+    let proof = proof_pda(pool).0;
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(bus, false),
+            AccountMeta::new_readonly(CONFIG_ADDRESS, false),
+            AccountMeta::new(pool, false),
+            AccountMeta::new(proof, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+            AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+            AccountMeta::new_readonly(EVENT_AUTHORITY_ADDRESS, false),
+            AccountMeta::new_readonly(crate::id(), false),
+        ],
+        data: PoolMine {}.to_bytes(),
+    }
+}
+
+/// Builds a pool settle instruction. Members are the authorities of the member accounts to settle.
+pub fn pool_settle(signer: Pubkey, pool: Pubkey, members: &[Pubkey]) -> Instruction {
+    let mut accounts = vec![AccountMeta::new(signer, true), AccountMeta::new(pool, false)];
+    accounts.extend(
+        members
+            .iter()
+            .map(|authority| AccountMeta::new(member_pda(pool, *authority).0, false)),
+    );
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: PoolSettle {}.to_bytes(),
+    }
+}
+
+/// Builds a pool claim instruction.
+pub fn pool_claim(
+    signer: Pubkey,
+    beneficiary: Pubkey,
+    pool: Pubkey,
+    amount: u64,
+    token_program: Pubkey,
+) -> Instruction {
+    let member = member_pda(pool, signer).0;
+    let proof = proof_pda(pool).0;
+    let treasury_tokens = treasury_tokens_address(&token_program);
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(beneficiary, false),
+            AccountMeta::new_readonly(CONFIG_ADDRESS, false),
+            AccountMeta::new(pool, false),
+            AccountMeta::new(member, false),
+            AccountMeta::new(proof, false),
+            AccountMeta::new(TREASURY_ADDRESS, false),
+            AccountMeta::new(treasury_tokens, false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(MINT_ADDRESS, false),
+            AccountMeta::new_readonly(EVENT_AUTHORITY_ADDRESS, false),
+            AccountMeta::new_readonly(crate::id(), false),
+            AccountMeta::new(fee_vault_tokens_address(&token_program), false),
+        ],
+        data: PoolClaim {
+            amount: amount.to_le_bytes(),
+        }
+            .to_bytes(),
+    }
+}
+
 // Build an update instruction.
 pub fn update(signer: Pubkey, miner: Pubkey) -> Instruction {
     let proof = proof_pda(signer).0;
@@ -192,13 +803,21 @@ pub fn update(signer: Pubkey, miner: Pubkey) -> Instruction {
             AccountMeta::new(signer, true),
             AccountMeta::new_readonly(miner, false),
             AccountMeta::new(proof, false),
+            AccountMeta::new_readonly(EVENT_AUTHORITY_ADDRESS, false),
+            AccountMeta::new_readonly(crate::id(), false),
         ],
         data: Update {}.to_bytes(),
     }
 }
 
 // Build an upgrade instruction.
-pub fn upgrade(signer: Pubkey, beneficiary: Pubkey, sender: Pubkey, amount: u64) -> Instruction {
+pub fn upgrade(
+    signer: Pubkey,
+    beneficiary: Pubkey,
+    sender: Pubkey,
+    amount: u64,
+    token_program: Pubkey,
+) -> Instruction {
     Instruction {
         program_id: crate::id(),
         accounts: vec![
@@ -208,6 +827,10 @@ pub fn upgrade(signer: Pubkey, beneficiary: Pubkey, sender: Pubkey, amount: u64)
             AccountMeta::new(MINT_V1_ADDRESS, false),
             AccountMeta::new(sender, false),
             AccountMeta::new(TREASURY_ADDRESS, false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(CONFIG_ADDRESS, false),
+            AccountMeta::new_readonly(EVENT_AUTHORITY_ADDRESS, false),
+            AccountMeta::new_readonly(crate::id(), false),
             AccountMeta::new_readonly(spl_token::id(), false),
         ],
         data: Upgrade {
@@ -216,20 +839,19 @@ pub fn upgrade(signer: Pubkey, beneficiary: Pubkey, sender: Pubkey, amount: u64)
             .to_bytes(),
     }
 }
-/// 构建初始化指令。
-pub fn initialize(signer: Pubkey) -> Instruction {
-    // 数组，用于存储公共总线 PDA（程序派生地址）
-    let bus_pdas = [
-        bus_pda(0),
-        bus_pda(1),
-        bus_pda(2),
-        bus_pda(3),
-        bus_pda(4),
-        bus_pda(5),
-        bus_pda(6),
-        bus_pda(7),
-    ];
-
+/// 构建初始化指令。代币的名称、符号和 URI 写入铸币的元数据。
+/// `extensions` 为 `MINT_EXTENSION_*` 标志位，非零时 `token_program` 必须为 Token-2022。
+#[allow(clippy::too_many_arguments)]
+pub fn initialize(
+    signer: Pubkey,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+    token_program: Pubkey,
+    extensions: u8,
+    transfer_fee_basis_points: u16,
+    maximum_fee: u64,
+) -> Instruction {
     // 获取配置 PDA
     let config_pda = config_pda();
 
@@ -240,7 +862,11 @@ pub fn initialize(signer: Pubkey) -> Instruction {
     let treasury_pda = treasury_pda();
 
     // 获取财政的关联代币地址
-    let treasury_tokens = spl_associated_token_account::get_associated_token_address(&treasury_pda.0, &mint_pda.0);
+    let treasury_tokens = spl_associated_token_account::get_associated_token_address_with_program_id(
+        &treasury_pda.0,
+        &mint_pda.0,
+        &token_program,
+    );
 
     // 使用程序地址派生找到元数据 PDA
     let metadata_pda = Pubkey::find_program_address(
@@ -257,15 +883,6 @@ pub fn initialize(signer: Pubkey) -> Instruction {
         program_id: crate::id(), // 程序的 ID
         accounts: vec![
             AccountMeta::new(signer, true), // 指令的签名者
-            // 将总线 PDA 添加到指令中
-            AccountMeta::new(bus_pdas[0].0, false),
-            AccountMeta::new(bus_pdas[1].0, false),
-            AccountMeta::new(bus_pdas[2].0, false),
-            AccountMeta::new(bus_pdas[3].0, false),
-            AccountMeta::new(bus_pdas[4].0, false),
-            AccountMeta::new(bus_pdas[5].0, false),
-            AccountMeta::new(bus_pdas[6].0, false),
-            AccountMeta::new(bus_pdas[7].0, false),
             AccountMeta::new(config_pda.0, false), // 配置 PDA
             AccountMeta::new(metadata_pda.0, false), // 元数据 PDA
             AccountMeta::new(mint_pda.0, false), // 铸币 PDA
@@ -273,21 +890,12 @@ pub fn initialize(signer: Pubkey) -> Instruction {
             AccountMeta::new(treasury_tokens, false), // 财政代币关联地址
             // 只读账户（此指令不会修改）
             AccountMeta::new_readonly(system_program::id(), false),
-            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(token_program, false),
             AccountMeta::new_readonly(spl_associated_token_account::id(), false),
             AccountMeta::new_readonly(mpl_token_metadata::ID, false),
             AccountMeta::new_readonly(sysvar::rent::id(), false),
         ],
         data: Initialize {
-            // 每个总线 PDA 的 bump 值
-            bus_0_bump: bus_pdas[0].1,
-            bus_1_bump: bus_pdas[1].1,
-            bus_2_bump: bus_pdas[2].1,
-            bus_3_bump: bus_pdas[3].1,
-            bus_4_bump: bus_pdas[4].1,
-            bus_5_bump: bus_pdas[5].1,
-            bus_6_bump: bus_pdas[6].1,
-            bus_7_bump: bus_pdas[7].1,
             // 配置 PDA 的 bump 值
             config_bump: config_pda.1,
             // 元数据 PDA 的 bump 值
@@ -296,6 +904,220 @@ pub fn initialize(signer: Pubkey) -> Instruction {
             mint_bump: mint_pda.1,
             // 财政 PDA 的 bump 值
             treasury_bump: treasury_pda.1,
+            // 代币元数据
+            name: to_fixed_bytes(name),
+            symbol: to_fixed_bytes(symbol),
+            uri: to_fixed_bytes(uri),
+            // 铸币扩展
+            extensions,
+            transfer_fee_basis_points: transfer_fee_basis_points.to_le_bytes(),
+            maximum_fee: maximum_fee.to_le_bytes(),
         }.to_bytes(), // 将 Initialize 数据转换为字节以用于指令
     }
 }
+
+/// 构建添加 bus 指令。`id` 必须等于当前的 `Config.bus_count`。早期部署中已经存在的 bus 同样按顺序添加。
+pub fn add_bus(signer: Pubkey, id: u8) -> Instruction {
+    let bus_pda = bus_pda(id);
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(CONFIG_ADDRESS, false),
+            AccountMeta::new(bus_pda.0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: AddBus { bump: bus_pda.1 }.to_bytes(),
+    }
+}
+
+/// Builds an update metadata instruction. On deployments whose metadata is still controlled by the
+/// initializer, the signer must be that key and the update authority is handed over to the treasury.
+pub fn update_metadata(signer: Pubkey, name: &str, symbol: &str, uri: &str) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(CONFIG_ADDRESS, false),
+            AccountMeta::new(METADATA_ADDRESS, false),
+            AccountMeta::new_readonly(TREASURY_ADDRESS, false),
+            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+        ],
+        data: UpdateMetadata {
+            name: to_fixed_bytes(name),
+            symbol: to_fixed_bytes(symbol),
+            uri: to_fixed_bytes(uri),
+        }
+            .to_bytes(),
+    }
+}
+
+/// 将字符串写入定长的零填充字节数组。超出长度的部分会被截断，并由程序拒绝。
+fn to_fixed_bytes<const N: usize>(s: &str) -> [u8; N] {
+    let mut bytes = [0u8; N];
+    let len = s.len().min(N);
+    bytes[..len].copy_from_slice(&s.as_bytes()[..len]);
+    bytes
+}
+
+/// Builds a set config instruction. The parameters are read from the provided config; the admin is
+/// transferred separately with `propose_admin` and `accept_admin`.
+pub fn set_config(signer: Pubkey, config: Config) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(CONFIG_ADDRESS, false),
+        ],
+        data: SetConfig {
+            epoch_duration: config.epoch_duration.to_le_bytes(),
+            tolerance: config.tolerance.to_le_bytes(),
+            base_reward_rate_min_threshold: config.base_reward_rate_min_threshold.to_le_bytes(),
+            base_reward_rate_max_threshold: config.base_reward_rate_max_threshold.to_le_bytes(),
+            smoothing_factor: config.smoothing_factor.to_le_bytes(),
+            max_hash_reward: config.max_hash_reward.to_le_bytes(),
+            unstake_cooldown: config.unstake_cooldown.to_le_bytes(),
+            jackpot_rate: config.jackpot_rate.to_le_bytes(),
+            jackpot_difficulty: config.jackpot_difficulty.to_le_bytes(),
+            jackpot_odds: config.jackpot_odds.to_le_bytes(),
+            tail_emission: config.tail_emission.to_le_bytes(),
+            emission_curve: config.emission_curve.to_le_bytes(),
+            emission_interval: config.emission_interval.to_le_bytes(),
+            emission_decay: config.emission_decay.to_le_bytes(),
+            reset_bounty: config.reset_bounty.to_le_bytes(),
+            auth_mode: config.auth_mode.to_le_bytes(),
+            claim_fee_bps: config.claim_fee_bps.to_le_bytes(),
+            reap_period: config.reap_period.to_le_bytes(),
+        }
+            .to_bytes(),
+    }
+}
+
+/// Builds a migrate config instruction. A config created before the admin field existed can only
+/// be migrated by the initializer.
+pub fn migrate_config(signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(CONFIG_ADDRESS, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: MigrateConfig {}.to_bytes(),
+    }
+}
+
+/// Builds a propose admin instruction. Proposing `Pubkey::default()` cancels a pending transfer.
+pub fn propose_admin(signer: Pubkey, new_admin: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(new_admin, false),
+            AccountMeta::new(CONFIG_ADDRESS, false),
+        ],
+        data: ProposeAdmin {}.to_bytes(),
+    }
+}
+
+/// Builds an accept admin instruction. The signer must be the pending admin.
+pub fn accept_admin(signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(CONFIG_ADDRESS, false),
+        ],
+        data: AcceptAdmin {}.to_bytes(),
+    }
+}
+
+/// Builds a migrate treasury instruction. Legacy treasuries are resized to the current layout and
+/// their liabilities are seeded from the treasury token balance, excluding unmined rewards. All
+/// `bus_count` busses must be passed in order.
+pub fn migrate_treasury(signer: Pubkey, token_program: Pubkey, bus_count: u64) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new_readonly(CONFIG_ADDRESS, false),
+        AccountMeta::new(TREASURY_ADDRESS, false),
+        AccountMeta::new_readonly(treasury_tokens_address(&token_program), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend(
+        BUS_ADDRESSES
+            .iter()
+            .take(bus_count as usize)
+            .map(|bus| AccountMeta::new_readonly(*bus, false)),
+    );
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: MigrateTreasury {}.to_bytes(),
+    }
+}
+
+/// Builds a create fee vault instruction. The fee vault token account is the associated token
+/// account of the fee vault PDA and must exist before a nonzero claim fee is configured.
+pub fn create_fee_vault(signer: Pubkey, token_program: Pubkey) -> Instruction {
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+        &signer,
+        &FEE_VAULT_ADDRESS,
+        &MINT_ADDRESS,
+        &token_program,
+    )
+}
+
+/// Builds a withdraw fees instruction.
+pub fn withdraw_fees(
+    signer: Pubkey,
+    beneficiary: Pubkey,
+    amount: u64,
+    token_program: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(beneficiary, false),
+            AccountMeta::new_readonly(CONFIG_ADDRESS, false),
+            AccountMeta::new_readonly(FEE_VAULT_ADDRESS, false),
+            AccountMeta::new(fee_vault_tokens_address(&token_program), false),
+            AccountMeta::new_readonly(MINT_ADDRESS, false),
+            AccountMeta::new_readonly(token_program, false),
+        ],
+        data: WithdrawFees {
+            amount: amount.to_le_bytes(),
+        }
+            .to_bytes(),
+    }
+}
+
+/// Builds a pause instruction.
+pub fn pause(signer: Pubkey, flags: u64) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(CONFIG_ADDRESS, false),
+        ],
+        data: Pause {
+            flags: flags.to_le_bytes(),
+        }
+            .to_bytes(),
+    }
+}
+
+/// Builds an unpause instruction.
+pub fn unpause(signer: Pubkey, flags: u64) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(CONFIG_ADDRESS, false),
+        ],
+        data: Unpause {
+            flags: flags.to_le_bytes(),
+        }
+            .to_bytes(),
+    }
+}
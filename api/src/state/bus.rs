@@ -6,7 +6,7 @@ use crate::consts::BUS;  // 引入常量 BUS
 
 use super::LuckycoinAccount;  // 引入父模块中的 OreAccount
 
-/// Bus 账户负责分配挖矿奖励。总共有 8 个 bus 账户，
+/// Bus 账户负责分配挖矿奖励。管理员可以添加最多 MAX_BUS_COUNT 个 bus 账户，
 /// 以最小化写锁争用并允许 Solana 并行处理挖矿指令。
 #[repr(C)]  // 指定为 C 语言兼容的内存布局
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]  // 实现相关 trait
@@ -23,6 +23,12 @@ pub struct Bus {
 
     /// 在当前周期内，bus 观察到的最大质押余额。
     pub top_balance: u64,
+
+    /// 该 bus 累积的幸运奖池余额。每次重置时从 bus 的周期发行量中划出一部分注入奖池。
+    pub jackpot: u64,
+
+    /// 自上次重置以来该 bus 记入 proof 账户的奖励总额，重置时结算到财库的负债中。
+    pub liabilities: u64,
 }
 
 /// 获取 bus 账户的程序派生地址 (PDA)。
@@ -7,6 +7,8 @@ use crate::consts::CONFIG;  // 引入常量 CONFIG
 use super::LuckycoinAccount;  // 引入父模块中的 LuckycoinAccount
 
 /// Config 是一个单例账户，用于管理程序的全局变量。
+///
+/// 新增字段只能追加在结构体末尾，已部署的旧账户通过 MigrateConfig 扩容。
 #[repr(C)]  // 指定为 C 语言兼容的内存布局
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]  // 实现相关 trait
 pub struct Config {
@@ -21,6 +23,92 @@ pub struct Config {
 
     /// 上一个周期内网络上观察到的最大质押余额。
     pub top_balance: u64,
+
+    /// 允许修改配置参数的管理员。
+    pub admin: Pubkey,
+
+    /// 程序周期的持续时间（秒）。
+    pub epoch_duration: i64,
+
+    /// 允许的垃圾邮件/活跃性容忍时间（秒）。
+    pub tolerance: i64,
+
+    /// 允许的最低基础奖励率，低于此值时应提高最低难度。
+    pub base_reward_rate_min_threshold: u64,
+
+    /// 允许的最高基础奖励率，超过此值时应降低最低难度。
+    pub base_reward_rate_max_threshold: u64,
+
+    /// 奖励率变化的平滑因子。
+    pub smoothing_factor: u64,
+
+    /// 单个哈希可获得的最大奖励。
+    pub max_hash_reward: u64,
+
+    /// 已暂停指令的标志位集合。
+    pub paused: u64,
+
+    /// 最后一次质押之后，必须等待多久才能解除质押（秒）。
+    pub unstake_cooldown: i64,
+
+    /// 每个周期划入幸运奖池的发行量比例（基点）。
+    pub jackpot_rate: u64,
+
+    /// 直接赢得幸运奖池所需的最低难度。为 0 时禁用。
+    pub jackpot_difficulty: u64,
+
+    /// 每个准时提交的有效哈希通过抽奖赢得幸运奖池的概率为 1/jackpot_odds。
+    pub jackpot_odds: u64,
+
+    /// 达到最大供应量之后，每个周期允许铸造的尾部发行量。
+    pub tail_emission: u64,
+
+    /// 捐赠到国库、尚未分配给 bus 的奖励。铸造不足以补充 bus 时从中支出。
+    pub reward_pool: u64,
+
+    /// 发行曲线的类型：固定、减半或指数衰减。
+    pub emission_curve: u64,
+
+    /// 发行曲线每一步的周期数。
+    pub emission_interval: u64,
+
+    /// 指数衰减曲线每一步保留的发行量比例（基点）。
+    pub emission_decay: u64,
+
+    /// 自程序启动以来已经完成的周期数。
+    pub epoch_count: u64,
+
+    /// 每个周期第一次成功重置时支付给受益人代币账户的赏金，从该周期新铸造的代币中划出，不额外增发。
+    pub reset_bounty: u64,
+
+    /// 已创建的 bus 账户数量。
+    pub bus_count: u64,
+
+    /// 挖矿时认证 proof 账户的方式：空操作程序、ed25519 签名或不认证。
+    pub auth_mode: u64,
+
+    /// 每次领取时划入手续费金库的比例（基点）。为 0 时不收取手续费。
+    pub claim_fee_bps: u64,
+
+    /// proof 账户闲置超过该时间（秒）且余额为零后，任何人都可以回收它。为 0 时禁用。
+    pub reap_period: i64,
+
+    /// 待确认的新管理员。为默认公钥时没有待处理的转移。
+    pub pending_admin: Pubkey,
+}
+
+impl Config {
+    /// 检查给定的指令标志位是否已被暂停。
+    pub fn is_paused(&self, flag: u64) -> bool {
+        self.paused & flag != 0
+    }
+
+    /// 从任意版本的配置账户数据中读取管理员。管理员字段出现之前的旧配置返回 `None`。
+    pub fn admin_from_bytes(data: &[u8]) -> Option<Pubkey> {
+        let offset = 8 + bytemuck::offset_of!(Config::zeroed(), Config, admin);
+        let bytes = data.get(offset..offset + std::mem::size_of::<Pubkey>())?;
+        Pubkey::try_from(bytes).ok()
+    }
 }
 
 /// 计算配置账户的程序派生地址 (PDA)。
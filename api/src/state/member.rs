@@ -0,0 +1,44 @@
+use bytemuck::{Pod, Zeroable};  // 引入 bytemuck 库以支持零拷贝数据结构
+use solana_program::pubkey::Pubkey;  // 引入 Solana 的 Pubkey 类型
+use steel::*;  // 引入 steel 库，可能用于处理指令和账户元数据
+
+use crate::consts::MEMBER;  // 引入常量 MEMBER
+
+use super::LuckycoinAccount;  // 引入父模块中的 LuckycoinAccount
+
+/// Member 账户记录矿池成员每轮贡献的工作量和可领取的奖励。
+#[repr(C)]  // 指定为 C 语言兼容的内存布局
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]  // 实现相关 trait
+pub struct Member {
+    /// 授权使用此成员账户的签名者。
+    pub authority: Pubkey,
+
+    /// 成员所属的矿池。
+    pub pool: Pubkey,
+
+    /// 成员可从矿池领取的代币数量。
+    pub balance: u64,
+
+    /// 记录工作量的轮次。
+    pub round: u64,
+
+    /// 成员在 `round` 轮次中贡献的工作量。
+    pub work: u64,
+
+    /// 成员贡献的总工作量。
+    pub total_work: u64,
+
+    /// 分配给该成员的总奖励数量。
+    pub total_rewards: u64,
+
+    /// 成员在矿池中的编号。成员提交的随机数的高 32 位必须等于该编号。
+    pub id: u64,
+}
+
+/// 计算给定矿池和授权者的 member 账户的程序派生地址 (PDA)。
+pub fn member_pda(pool: Pubkey, authority: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MEMBER, pool.as_ref(), authority.as_ref()], &crate::id())
+}
+
+// 为 LuckycoinAccount 生成与 Member 结构体相关的账户实现。
+account!(LuckycoinAccount, Member);
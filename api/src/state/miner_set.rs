@@ -0,0 +1,42 @@
+use bytemuck::{Pod, Zeroable};  // 引入 bytemuck 库以支持零拷贝数据结构
+use solana_program::pubkey::Pubkey;  // 引入 Solana 的 Pubkey 类型
+use steel::*;  // 引入 steel 库，可能用于处理指令和账户元数据
+
+use crate::consts::{MAX_MINERS, MINER_SET};  // 引入 miner set 相关的常量
+
+use super::LuckycoinAccount;  // 引入父模块中的 LuckycoinAccount
+
+/// MinerSet 账户是 proof 账户的扩展，列出除 `Proof.miner` 之外被授权使用该 proof 挖矿的密钥。
+/// 每个密钥可以设置一个过期插槽，过期后自动失效。
+#[repr(C)]  // 指定为 C 语言兼容的内存布局
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]  // 实现相关 trait
+pub struct MinerSet {
+    /// 此 miner set 所属的 proof 账户。
+    pub proof: Pubkey,
+
+    /// 被授权的矿工密钥。空位为默认公钥。
+    pub miners: [Pubkey; MAX_MINERS],
+
+    /// 每个矿工密钥的过期插槽。为 0 时永不过期。
+    pub expires_at: [u64; MAX_MINERS],
+}
+
+impl MinerSet {
+    /// 检查给定的密钥在给定插槽是否被授权挖矿。
+    pub fn is_authorized(&self, miner: &Pubkey, slot: u64) -> bool {
+        self.miners
+            .iter()
+            .zip(self.expires_at.iter())
+            .any(|(key, expires_at)| {
+                key.eq(miner) && (expires_at.eq(&0) || expires_at.gt(&slot))
+            })
+    }
+}
+
+/// 计算给定 proof 账户的 miner set 账户的程序派生地址 (PDA)。
+pub fn miner_set_pda(proof: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINER_SET, proof.as_ref()], &crate::id())
+}
+
+// 为 LuckycoinAccount 生成与 MinerSet 结构体相关的账户实现。
+account!(LuckycoinAccount, MinerSet);
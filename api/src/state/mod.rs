@@ -1,11 +1,19 @@
 mod bus;
 mod config;
+mod member;
+mod miner_set;
+mod pool;
 mod proof;
+mod session;
 mod treasury;
 
 pub use bus::*;
 pub use config::*;
+pub use member::*;
+pub use miner_set::*;
+pub use pool::*;
 pub use proof::*;
+pub use session::*;
 pub use treasury::*;
 
 use num_enum::{IntoPrimitive, TryFromPrimitive};
@@ -17,4 +25,8 @@ pub enum LuckycoinAccount {
     Config = 101,
     Proof = 102,
     Treasury = 103,
+    Pool = 104,
+    Member = 105,
+    MinerSet = 106,
+    Session = 107,
 }
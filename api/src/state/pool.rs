@@ -0,0 +1,58 @@
+use bytemuck::{Pod, Zeroable};  // 引入 bytemuck 库以支持零拷贝数据结构
+use solana_program::pubkey::Pubkey;  // 引入 Solana 的 Pubkey 类型
+use steel::*;  // 引入 steel 库，可能用于处理指令和账户元数据
+
+use crate::consts::POOL;  // 引入常量 POOL
+
+use super::LuckycoinAccount;  // 引入父模块中的 LuckycoinAccount
+
+/// Pool 账户拥有一个 proof 账户，并汇总成员提交的解决方案。
+/// 运营者每轮提交成员中最好的解决方案，奖励按成员贡献的工作量在链上按比例分配。
+/// 每轮结束时只记录该轮的奖励和工作量，成员的份额之后按需逐个结算。
+#[repr(C)]  // 指定为 C 语言兼容的内存布局
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]  // 实现相关 trait
+pub struct Pool {
+    /// 允许提交矿池挖矿的运营者。
+    pub operator: Pubkey,
+
+    /// 矿池拥有的 proof 账户。
+    pub proof: Pubkey,
+
+    /// 当前的轮次。每次矿池挖矿成功后递增。
+    pub round: u64,
+
+    /// 当前轮次中提交最好解决方案的成员账户。
+    pub best_member: Pubkey,
+
+    /// 当前轮次中最好解决方案的摘要。
+    pub best_digest: [u8; 16],
+
+    /// 当前轮次中最好解决方案的随机数。
+    pub best_nonce: [u8; 8],
+
+    /// 当前轮次中最好解决方案的难度。
+    pub best_difficulty: u64,
+
+    /// 当前轮次中所有成员贡献的工作量总和。
+    pub total_work: u64,
+
+    /// 加入矿池的成员总数。
+    pub total_members: u64,
+
+    /// 矿池获得的总奖励数量。
+    pub total_rewards: u64,
+
+    /// 上一轮尚未结算给成员的奖励。
+    pub unsettled_rewards: u64,
+
+    /// 上一轮尚未结算的成员工作量。为零之前不能提交下一轮。
+    pub unsettled_work: u64,
+}
+
+/// 计算给定运营者的 pool 账户的程序派生地址 (PDA)。
+pub fn pool_pda(operator: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL, operator.as_ref()], &crate::id())
+}
+
+// 为 LuckycoinAccount 生成与 Pool 结构体相关的账户实现。
+account!(LuckycoinAccount, Pool);
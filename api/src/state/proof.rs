@@ -14,8 +14,9 @@ pub struct Proof {
     /// 授权使用此 proof 账户的签名者。
     pub authority: Pubkey,
 
-    /// 矿工已质押或获得的代币数量。
-    pub balance: u64,
+    /// 矿工通过挖矿获得、尚未领取的代币数量。
+    /// 旧版本账户中的 `balance` 位于同一位置，迁移时其中的挖矿收益保留在此，其余部分转入质押本金。
+    pub earnings: u64,
 
     /// 当前的挖矿挑战。
     pub challenge: [u8; 32],
@@ -37,6 +38,31 @@ pub struct Proof {
 
     /// 分配给该矿工的总奖励数量。
     pub total_rewards: u64,
+
+    /// 矿工已质押的本金数量。
+    pub stake: u64,
+
+    /// 质押的锁定档位，对应 `LOCK_DURATIONS` 和 `LOCK_MULTIPLIERS` 的索引。
+    pub lock_tier: u64,
+
+    /// 质押锁定到期的时间戳。在此之前不能解除质押。
+    pub lock_until: i64,
+
+    /// 等待接受转移的新授权者。默认值表示没有待处理的转移。
+    pub pending_authority: Pubkey,
+
+    /// 尚未开奖的抽奖券的种子，即获得抽奖券的哈希。
+    pub jackpot_seed: [u8; 32],
+
+    /// 获得抽奖券的插槽，抽奖券使用该插槽的插槽哈希开奖。为 0 时表示没有抽奖券。
+    pub jackpot_slot: u64,
+
+    /// 与此 proof 账户关联、尚未关闭的 miner set 和 session 账户数量。
+    /// 不为 0 时 proof 账户不能被关闭，关闭时必须一并提供这些账户。
+    pub linked_accounts: u64,
+
+    /// 获得抽奖券的 bus 编号。抽奖券只能在向该 bus 提交时开奖，并且只从该 bus 的幸运奖池中支付。
+    pub jackpot_bus: u64,
 }
 
 /// 计算给定矿工的 proof 账户的程序派生地址 (PDA)。
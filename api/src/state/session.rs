@@ -0,0 +1,46 @@
+use bytemuck::{Pod, Zeroable};  // 引入 bytemuck 库以支持零拷贝数据结构
+use solana_program::pubkey::Pubkey;  // 引入 Solana 的 Pubkey 类型
+use steel::*;  // 引入 steel 库，可能用于处理指令和账户元数据
+
+use crate::consts::SESSION;  // 引入常量 SESSION
+
+use super::LuckycoinAccount;  // 引入父模块中的 LuckycoinAccount
+
+/// Session 账户记录 proof 授权者委托给会话密钥的临时权限。
+/// 会话密钥在过期之前可以使用 proof 账户挖矿，并可以向固定的受益人领取有上限的奖励。
+#[repr(C)]  // 指定为 C 语言兼容的内存布局
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]  // 实现相关 trait
+pub struct Session {
+    /// 此会话所属的 proof 账户。
+    pub proof: Pubkey,
+
+    /// 被委托的会话密钥。
+    pub key: Pubkey,
+
+    /// 会话密钥领取奖励时唯一允许的受益人代币账户。
+    pub beneficiary: Pubkey,
+
+    /// 会话过期的时间戳。
+    pub expires_at: i64,
+
+    /// 会话密钥最多可以领取的奖励数量。为 0 时不允许领取。
+    pub claim_limit: u64,
+
+    /// 会话密钥已经领取的奖励数量。
+    pub claimed: u64,
+}
+
+impl Session {
+    /// 检查会话在给定时间是否仍然有效。
+    pub fn is_active(&self, t: i64) -> bool {
+        self.expires_at.gt(&t)
+    }
+}
+
+/// 计算给定 proof 账户和会话密钥的 session 账户的程序派生地址 (PDA)。
+pub fn session_pda(proof: Pubkey, key: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SESSION, proof.as_ref(), key.as_ref()], &crate::id())
+}
+
+// 为 LuckycoinAccount 生成与 Session 结构体相关的账户实现。
+account!(LuckycoinAccount, Session);
@@ -7,9 +7,27 @@ use crate::consts::TREASURY;  // 引入常量 TREASURY
 use super::LuckycoinAccount;  // 引入父模块中的 LuckycoinAccount
 
 /// Treasury 是一个单例账户，作为 ORE 代币的铸币权限和程序全局代币账户的权限。
+///
+/// 财库同时记录对所有 proof 账户的负债。挖矿奖励先累积在 bus 账户中，每次重置时结算到财库，
+/// 以避免挖矿指令对财库加写锁。
 #[repr(C)]  // 指定为 C 语言兼容的内存布局
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]  // 实现相关 trait
-pub struct Treasury {}
+pub struct Treasury {
+    /// 累计记入 proof 账户的代币总额，包括已结算的挖矿奖励和质押本金。
+    pub total_credited: u64,
+
+    /// 累计从 proof 账户支出的代币总额，包括领取和解除质押。
+    pub total_debited: u64,
+}
+
+impl Treasury {
+    /// 返回财库对所有 proof 账户的未偿负债。`pending` 是 bus 账户中尚未结算的挖矿奖励。
+    pub fn liabilities(&self, pending: u64) -> u64 {
+        self.total_credited
+            .saturating_add(pending)
+            .saturating_sub(self.total_debited)
+    }
+}
 
 /// 计算财政账户的程序派生地址 (PDA)。
 pub fn treasury_pda() -> (Pubkey, u8) {
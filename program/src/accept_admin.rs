@@ -0,0 +1,29 @@
+use luckycoin_api::{loaders::*, state::Config};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use steel::*;
+
+/// AcceptAdmin 完成管理员转移，签名者必须是 ProposeAdmin 记录的待处理管理员。
+pub fn process_accept_admin(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer, config_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_config(config_info, true)?;
+
+    // 验证签名者是待处理的管理员
+    let mut config_data = config_info.data.borrow_mut();
+    let config = Config::try_from_bytes_mut(&mut config_data)?;
+    if config.pending_admin.eq(&Pubkey::default()) || config.pending_admin.ne(signer.key) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // 更新管理员
+    config.admin = *signer.key;
+    config.pending_admin = Pubkey::default();
+
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use std::mem::size_of;
+
+use luckycoin_api::{
+    consts::*,
+    cpi::create_pda,
+    error::LuckycoinError,
+    instruction::AcceptAuthority,
+    loaders::*,
+    state::Proof,
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey, system_program,
+};
+use steel::*;
+
+use crate::close::close_linked_accounts;
+
+/// AcceptAuthority 完成授权者转移。
+///
+/// 由于 proof 账户的地址由授权者派生，转移时会在新授权者的 PDA 上创建一个新的 proof 账户，
+/// 复制全部状态（余额、质押、锁定和生命周期统计），然后关闭旧账户并将其租金退还给旧授权者。
+/// 旧账户关联的 miner set 和 session 账户必须排在最后一并提供，它们会被关闭，不会转移给新授权者，
+/// 租金同样退还给旧授权者。
+/// 旧授权者设置的矿工同样不会保留，新授权者成为新账户的矿工，之后可以通过 Update 重新指定。
+pub fn process_accept_authority(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = AcceptAuthority::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [
+        signer,
+        old_authority_info,
+        proof_info,
+        new_proof_info,
+        system_program,
+        linked_infos @ ..
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any_proof(proof_info, true)?;
+    load_uninitialized_pda(
+        new_proof_info,
+        &[PROOF, signer.key.as_ref()],
+        args.bump,
+        &luckycoin_api::id(),
+    )?;
+    load_program(system_program, system_program::id())?;
+
+    // 关闭旧账户的所有关联账户，然后生成转移后的状态
+    let proof_data = proof_info.data.borrow();
+    let mut proof = *Proof::try_from_bytes(&proof_data)?;
+    drop(proof_data);
+
+    // 租金退还给旧授权者
+    if proof.authority.ne(old_authority_info.key) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    close_linked_accounts(proof_info.key, &mut proof, linked_infos, old_authority_info)?;
+    let proof = accept_proof(&proof, signer.key)?;
+
+    // 在新授权者的 PDA 上创建 proof 账户
+    create_pda(
+        new_proof_info,
+        &luckycoin_api::id(),
+        8 + size_of::<Proof>(),
+        &[PROOF, signer.key.as_ref(), &[args.bump]],
+        system_program,
+        signer,
+    )?;
+
+    // 写入转移后的状态
+    let mut new_proof_data = new_proof_info.data.borrow_mut();
+    new_proof_data[0] = Proof::discriminator() as u8;
+    *Proof::try_from_bytes_mut(&mut new_proof_data)? = proof;
+    drop(new_proof_data);
+
+    // 将旧的证明账户数据重新分配为零
+    proof_info.realloc(0, true)?;
+
+    // 将旧账户剩余的 lamports 退还给旧授权者
+    **old_authority_info.lamports.borrow_mut() += proof_info.lamports();
+    **proof_info.lamports.borrow_mut() = 0;
+
+    Ok(())
+}
+
+/// 生成转移给 `new_authority` 之后的 proof 状态。
+///
+/// `new_authority` 必须是待处理的授权者，并且旧账户的关联账户必须已经全部关闭。新授权者同时成为矿工，
+/// 其余状态保持不变。
+pub fn accept_proof(proof: &Proof, new_authority: &Pubkey) -> Result<Proof, ProgramError> {
+    // 验证签名者是待处理的授权者
+    if proof.pending_authority.eq(&Pubkey::default()) || proof.pending_authority.ne(new_authority) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // 关联账户不能转移给新授权者
+    if proof.linked_accounts.gt(&0) {
+        return Err(LuckycoinError::LinkedAccountsOpen.into());
+    }
+
+    let mut new_proof = *proof;
+    new_proof.authority = *new_authority;
+    new_proof.miner = *new_authority;
+    new_proof.pending_authority = Pubkey::default();
+    Ok(new_proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use luckycoin_api::{error::LuckycoinError, state::Proof};
+    use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+    use steel::*;
+
+    use super::accept_proof;
+
+    fn pending_proof(new_authority: Pubkey) -> Proof {
+        let mut proof = Proof::zeroed();
+        proof.authority = Pubkey::new_unique();
+        proof.miner = Pubkey::new_unique();
+        proof.pending_authority = new_authority;
+        proof.earnings = 10;
+        proof.stake = 20;
+        proof.lock_tier = 2;
+        proof
+    }
+
+    #[test]
+    fn test_accept_proof() {
+        let new_authority = Pubkey::new_unique();
+        let proof = pending_proof(new_authority);
+        let new_proof = accept_proof(&proof, &new_authority).unwrap();
+        assert_eq!(new_proof.authority, new_authority);
+        assert_eq!(new_proof.miner, new_authority);
+        assert_eq!(new_proof.pending_authority, Pubkey::default());
+        assert_eq!((new_proof.earnings, new_proof.stake, new_proof.lock_tier), (10, 20, 2));
+    }
+
+    #[test]
+    fn test_accept_proof_wrong_signer() {
+        let proof = pending_proof(Pubkey::new_unique());
+        assert_eq!(
+            accept_proof(&proof, &Pubkey::new_unique()),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        // 没有待处理的转移
+        let proof = pending_proof(Pubkey::default());
+        assert_eq!(
+            accept_proof(&proof, &Pubkey::default()),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_accept_proof_linked_accounts() {
+        let new_authority = Pubkey::new_unique();
+        let mut proof = pending_proof(new_authority);
+        proof.linked_accounts = 1;
+        assert_eq!(
+            accept_proof(&proof, &new_authority),
+            Err(LuckycoinError::LinkedAccountsOpen.into())
+        );
+    }
+}
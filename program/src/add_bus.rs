@@ -0,0 +1,137 @@
+use std::mem::size_of;
+
+use luckycoin_api::{
+    consts::*,
+    cpi::create_pda,
+    error::LuckycoinError,
+    instruction::AddBus,
+    loaders::*,
+    state::{Bus, Config},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    system_program,
+};
+use steel::*;
+
+use crate::migrate::realloc_account;
+
+/// AddBus 允许管理员创建下一个 bus 账户，以降低挖矿指令之间的写锁争用。
+///
+/// 新的 bus 从零奖励开始，在下一次重置时与其他 bus 一起补充。早期部署在初始化时已经创建了
+/// 固定数量的 bus 账户，而迁移后的配置从零开始计数。此时管理员按顺序对每个已有的 bus 调用
+/// 该指令，bus 账户被扩容到当前的数据布局并计入 bus 数量，原有的奖励保持不变。
+pub fn process_add_bus(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // 解析参数
+    let args = AddBus::try_from_bytes(data)?;
+
+    // 加载账户
+    let [signer, config_info, bus_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    // 加载配置账户，并确保签名者是当前管理员
+    load_config_with_admin(config_info, signer.key, true)?;
+    load_program(system_program, system_program::id())?;
+
+    // 验证 bus 数量未达到上限
+    let mut config_data = config_info.data.borrow_mut();
+    let config = Config::try_from_bytes_mut(&mut config_data)?;
+    let id = config.bus_count;
+    if id.ge(&(MAX_BUS_COUNT as u64)) {
+        return Err(LuckycoinError::BusCountInvalid.into());
+    }
+
+    if bus_info.owner.eq(&luckycoin_api::id()) {
+        // 接管早期部署中已经存在的 bus 账户
+        load_any_bus(bus_info, true)?;
+        if bus_info.key.ne(&BUS_ADDRESSES[id as usize]) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        realloc_account(signer, bus_info, system_program, 8 + size_of::<Bus>())?;
+        adopt_bus(&mut bus_info.data.borrow_mut(), id)?;
+    } else {
+        // 创建下一个 bus 账户
+        load_uninitialized_pda(bus_info, &[BUS, &[id as u8]], args.bump, &luckycoin_api::id())?;
+        create_pda(
+            bus_info,
+            &luckycoin_api::id(),
+            8 + size_of::<Bus>(),
+            &[BUS, &[id as u8], &[args.bump]],
+            system_program,
+            signer,
+        )?;
+        let mut bus_data = bus_info.data.borrow_mut();
+        bus_data[0] = Bus::discriminator() as u8;
+        let bus = Bus::try_from_bytes_mut(&mut bus_data)?;
+        bus.id = id;
+        bus.rewards = 0;
+        bus.theoretical_rewards = 0;
+        bus.top_balance = 0;
+        bus.jackpot = 0;
+        bus.liabilities = 0;
+    }
+
+    // 更新 bus 数量
+    config.bus_count = id.checked_add(1).unwrap();
+
+    Ok(())
+}
+
+/// 验证扩容后的已有 bus 账户可以作为第 `id` 个 bus 计入 bus 数量。
+///
+/// 早期的 bus 账户没有幸运奖池和负债字段，扩容时以零值初始化，其余字段保持不变。
+pub fn adopt_bus(data: &mut [u8], id: u64) -> Result<(), ProgramError> {
+    let bus = Bus::try_from_bytes_mut(data)?;
+    if bus.id.ne(&id) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::size_of;
+
+    use luckycoin_api::state::Bus;
+    use solana_program::program_error::ProgramError;
+    use steel::*;
+
+    use super::adopt_bus;
+
+    /// 早期的 bus 账户：判别符之后依次是 ID、剩余奖励、理论奖励和最大质押余额。
+    fn legacy_bus(id: u64, rewards: u64) -> Vec<u8> {
+        let mut data = vec![0u8; 8 + 8 * 4];
+        data[0] = Bus::discriminator() as u8;
+        data[8..16].copy_from_slice(&id.to_le_bytes());
+        data[16..24].copy_from_slice(&rewards.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_adopt_legacy_bus() {
+        let mut data = legacy_bus(3, 1_000);
+        data.resize(8 + size_of::<Bus>(), 0);
+        adopt_bus(&mut data, 3).unwrap();
+        let bus = Bus::try_from_bytes(&data).unwrap();
+        assert_eq!(bus.id, 3);
+        assert_eq!(bus.rewards, 1_000);
+        assert_eq!(bus.jackpot, 0);
+        assert_eq!(bus.liabilities, 0);
+    }
+
+    #[test]
+    fn test_adopt_bus_out_of_order() {
+        // bus 必须按 ID 顺序计入
+        let mut data = legacy_bus(3, 1_000);
+        data.resize(8 + size_of::<Bus>(), 0);
+        assert_eq!(adopt_bus(&mut data, 2), Err(ProgramError::InvalidAccountData));
+    }
+
+    #[test]
+    fn test_adopt_bus_not_migrated() {
+        // 未扩容的账户无法按当前布局解析
+        let mut data = legacy_bus(0, 1_000);
+        assert!(adopt_bus(&mut data, 0).is_err());
+    }
+}
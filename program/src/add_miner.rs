@@ -0,0 +1,88 @@
+use std::mem::size_of;
+
+use luckycoin_api::{
+    consts::*,
+    cpi::create_pda,
+    error::LuckycoinError,
+    instruction::AddMiner,
+    loaders::*,
+    state::{MinerSet, Proof},
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program_error::ProgramError, pubkey::Pubkey, system_program, sysvar::Sysvar,
+};
+use steel::*;
+
+/// AddMiner 授权一个额外的矿工密钥使用签名者的 proof 账户挖矿。
+///
+/// 如果 proof 账户还没有 miner set，则先创建它，并计入 proof 账户的关联账户数量。如果密钥已在集合中，则只更新其过期插槽。
+/// 已过期的密钥所占用的位置可以被新密钥复用。
+pub fn process_add_miner(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // 解析参数
+    let args = AddMiner::try_from_bytes(data)?;
+    let expires_at = u64::from_le_bytes(args.expires_at);
+
+    // 加载账户
+    let [signer, miner_info, miner_set_info, proof_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any(miner_info, false)?;
+    load_proof(proof_info, signer.key, true)?;
+    load_program(system_program, system_program::id())?;
+    if miner_info.key.eq(&Pubkey::default()) {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // 如果 miner set 尚不存在，则创建它
+    if miner_set_info.data_is_empty() {
+        load_uninitialized_pda(
+            miner_set_info,
+            &[MINER_SET, proof_info.key.as_ref()],
+            args.bump,
+            &luckycoin_api::id(),
+        )?;
+        create_pda(
+            miner_set_info,
+            &luckycoin_api::id(),
+            8 + size_of::<MinerSet>(),
+            &[MINER_SET, proof_info.key.as_ref(), &[args.bump]],
+            system_program,
+            signer,
+        )?;
+        let mut miner_set_data = miner_set_info.data.borrow_mut();
+        miner_set_data[0] = MinerSet::discriminator() as u8;
+        let miner_set = MinerSet::try_from_bytes_mut(&mut miner_set_data)?;
+        miner_set.proof = *proof_info.key;
+        miner_set.miners = [Pubkey::default(); MAX_MINERS];
+        miner_set.expires_at = [0; MAX_MINERS];
+
+        // 记录关联账户，关闭 proof 账户之前必须先关闭 miner set
+        let mut proof_data = proof_info.data.borrow_mut();
+        let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
+        proof.linked_accounts = proof.linked_accounts.checked_add(1).unwrap();
+    } else {
+        load_miner_set(miner_set_info, proof_info.key, true)?;
+    }
+
+    // 更新已有的密钥，否则占用一个空位或已过期的位置
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+    let mut miner_set_data = miner_set_info.data.borrow_mut();
+    let miner_set = MinerSet::try_from_bytes_mut(&mut miner_set_data)?;
+    let slot = miner_set
+        .miners
+        .iter()
+        .position(|key| key.eq(miner_info.key))
+        .or_else(|| {
+            (0..MAX_MINERS).find(|&i| {
+                miner_set.miners[i].eq(&Pubkey::default())
+                    || (miner_set.expires_at[i].gt(&0) && miner_set.expires_at[i].le(&clock.slot))
+            })
+        })
+        .ok_or(LuckycoinError::MinerSetFull)?;
+    miner_set.miners[slot] = *miner_info.key;
+    miner_set.expires_at[slot] = expires_at;
+
+    Ok(())
+}
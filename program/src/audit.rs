@@ -0,0 +1,52 @@
+use luckycoin_api::{
+    error::LuckycoinError,
+    loaders::*,
+    report::SolvencyReport,
+    state::{Bus, Config, Treasury},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program::set_return_data,
+    program_error::ProgramError,
+};
+use spl_token_2022::{extension::StateWithExtensions, state::Account};
+use steel::*;
+
+/// Audit 比较财库记录的负债与财库代币账户的余额，并将偿付能力报告写入返回数据。
+///
+/// 该指令只读，任何人都可以调用。所有 bus 账户都必须按顺序传入，以便计入尚未结算的挖矿奖励。
+pub fn process_audit(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // 加载账户
+    let [config_info, treasury_info, treasury_tokens_info, busses @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_config(config_info, false)?;
+    load_treasury(treasury_info, false)?;
+    load_treasury_tokens(treasury_tokens_info, false)?;
+
+    // 验证所有 bus 账户都已按顺序传入
+    let config_data = config_info.data.borrow();
+    let config = Config::try_from_bytes(&config_data)?;
+    if (busses.len() as u64).ne(&config.bus_count) {
+        return Err(LuckycoinError::BusCountInvalid.into());
+    }
+
+    // 累计 bus 中尚未结算的负债
+    let mut pending = 0u64;
+    for (i, bus_info) in busses.iter().enumerate() {
+        load_bus(bus_info, i as u64, false)?;
+        let bus_data = bus_info.data.borrow();
+        let bus = Bus::try_from_bytes(&bus_data)?;
+        pending = pending.checked_add(bus.liabilities).ok_or(LuckycoinError::Overflow)?;
+    }
+
+    // 生成报告
+    let treasury_data = treasury_info.data.borrow();
+    let treasury = Treasury::try_from_bytes(&treasury_data)?;
+    let balance = StateWithExtensions::<Account>::unpack(&treasury_tokens_info.data.borrow())?
+        .base
+        .amount;
+    let report = SolvencyReport::new(treasury.liabilities(pending), pending, balance);
+    set_return_data(report.to_bytes());
+
+    Ok(())
+}
@@ -1,9 +1,21 @@
-use luckycoin_api::{consts::*, error::LuckycoinError, instruction::*, loaders::*, state::Proof};
+use luckycoin_api::{
+    consts::*,
+    cpi::{emit_event, transfer_checked_signed},
+    error::LuckycoinError,
+    event::{ClaimEvent, EventHeader, LuckycoinEvent},
+    instruction::*,
+    loaders::*,
+    state::{Config, Proof, Session, Treasury},
+};
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program_error::ProgramError, sysvar::Sysvar,
 };
 use steel::*;
 
+/// Claim 从 proof 账户的收益中领取代币，按配置的比例收取手续费。
+///
+/// 会话账户和手续费金库代币账户是可选的尾部账户，按地址区分。手续费为 0 时不需要手续费金库。
 pub fn process_claim(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     let args = Claim::try_from_bytes(data)?;
     let amount = u64::from_le_bytes(args.amount);
@@ -14,7 +26,12 @@ pub fn process_claim(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
         proof_info, 
         treasury_info, 
         treasury_tokens_info, 
-        token_program
+        token_program,
+        config_info,
+        event_authority_info,
+        luckycoin_program,
+        mint_info,
+        optional_infos @ ..
     ] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys); 
     };
@@ -27,34 +44,140 @@ pub fn process_claim(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
         &MINT_ADDRESS, 
         true
     )?;
-    // 加载与签名者关联的证明账户
-    load_proof(proof_info, signer.key, true)?;
+    // 加载配置账户
+    load_config(config_info, false)?;
+    // 加载证明账户。签名者不是授权者时，由会话账户验证权限。
+    load_any_proof(proof_info, true)?;
     // 加载国库账户
-    load_treasury(treasury_info, false)?;
+    load_treasury(treasury_info, true)?;
     // 加载国库代币账户
     load_treasury_tokens(treasury_tokens_info, true)?;
-    // 加载SPL代币程序
-    load_program(token_program, spl_token::id())?;
+    // 加载铸币账户
+    load_mint(mint_info, MINT_ADDRESS, false)?;
+    // 加载代币程序，SPL Token 和 Token-2022 均可
+    load_token_program(token_program)?;
+    load_event_authority(event_authority_info)?;
+    load_program(luckycoin_program, luckycoin_api::id())?;
+
+    // 检查领取是否已被暂停
+    let config_data = config_info.data.borrow();
+    let config = Config::try_from_bytes(&config_data)?;
+    if config.is_paused(PAUSE_CLAIM) {
+        return Err(LuckycoinError::Paused.into());
+    }
+
+    // 计算领取手续费。手续费为 0 时不使用手续费金库，也不检查该账户。
+    let (fee, net) = calculate_claim_fee(amount, config.claim_fee_bps)?;
+    let fee_vault_tokens_info = load_optional_fee_vault_tokens(optional_infos, fee)?;
 
     // 可变借用证明账户的数据，以便更新余额。
     let mut proof_data = proof_info.data.borrow_mut();
     // 将证明数据反序列化Proof结构体
     let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
-    // 更新矿工的余额，通过减去认领的金额
-    proof.balance = proof
-        .balance
+
+    // 如果签名者不是授权者，则必须是有效会话的会话密钥，并且只能在上限内向会话的受益人领取
+    if proof.authority.ne(signer.key) {
+        let Some(session_info) = optional_infos.iter().find(|info| !is_fee_vault_tokens(info))
+        else {
+            return Err(LuckycoinError::SessionInvalid.into());
+        };
+        load_session(session_info, proof_info.key, signer.key, true)?;
+        let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+        let mut session_data = session_info.data.borrow_mut();
+        let session = Session::try_from_bytes_mut(&mut session_data)?;
+        if !session.is_active(clock.unix_timestamp)
+            || session.beneficiary.ne(beneficiary_info.key)
+        {
+            return Err(LuckycoinError::SessionInvalid.into());
+        }
+        session.claimed = session
+            .claimed
+            .checked_add(amount)
+            .filter(|claimed| claimed.le(&session.claim_limit))
+            .ok_or(LuckycoinError::SessionLimitExceeded)?;
+    }
+
+    // 更新矿工的收益，通过减去认领的金额。质押本金只能通过解除质押取回。
+    proof.earnings = proof
+        .earnings
         .checked_sub(amount)
         .ok_or(LuckycoinError::ClaimTooLarge)?; // 确保余额不会变为负数，如果金额过大则返回错误。
 
-    // 从国库向受益人账户转移代币。
-    transfer_signed(
+    // 记录财库的支出
+    let mut treasury_data = treasury_info.data.borrow_mut();
+    let treasury = Treasury::try_from_bytes_mut(&mut treasury_data)?;
+    treasury.total_debited = treasury
+        .total_debited
+        .checked_add(amount)
+        .ok_or(LuckycoinError::Overflow)?;
+    drop(treasury_data);
+
+    // 从国库向受益人账户转移扣除手续费后的代币。
+    transfer_checked_signed(
         treasury_info,
         treasury_tokens_info,
+        mint_info,
         beneficiary_info,
         token_program,
-        amount,
+        net,
         &[&[TREASURY, &[TREASURY_BUMP]]], // 用于转账的签名者种子。
     )?;
 
+    // 将手续费转入手续费金库
+    if let Some(fee_vault_tokens_info) = fee_vault_tokens_info {
+        transfer_checked_signed(
+            treasury_info,
+            treasury_tokens_info,
+            mint_info,
+            fee_vault_tokens_info,
+            token_program,
+            fee,
+            &[&[TREASURY, &[TREASURY_BUMP]]],
+        )?;
+    }
+
+    // 记录领取事件
+    let event = ClaimEvent {
+        header: EventHeader::new(LuckycoinEvent::Claim),
+        authority: proof.authority,
+        beneficiary: *beneficiary_info.key,
+        amount,
+        earnings: proof.earnings,
+        fee,
+        net,
+    };
+    emit_event(event_authority_info, luckycoin_program, event.to_bytes())?;
+
     Ok(()) // 返回成功。
-}
\ No newline at end of file
+}
+
+/// 计算领取 `amount` 时收取的手续费和受益人实际收到的金额。
+pub fn calculate_claim_fee(amount: u64, fee_bps: u64) -> Result<(u64, u64), LuckycoinError> {
+    let fee = (amount as u128)
+        .checked_mul(fee_bps.min(BPS_DENOMINATOR) as u128)
+        .and_then(|x| x.checked_div(BPS_DENOMINATOR as u128))
+        .ok_or(LuckycoinError::Overflow)? as u64;
+    let net = amount.checked_sub(fee).ok_or(LuckycoinError::Overflow)?;
+    Ok((fee, net))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::calculate_claim_fee;
+
+    #[test]
+    fn test_calculate_claim_fee() {
+        assert_eq!(calculate_claim_fee(10_000, 0), Ok((0, 10_000)));
+        assert_eq!(calculate_claim_fee(10_000, 250), Ok((250, 9_750)));
+
+        // 手续费向下取整，小额领取可能不收取手续费
+        assert_eq!(calculate_claim_fee(39, 250), Ok((0, 39)));
+        assert_eq!(calculate_claim_fee(41, 250), Ok((1, 40)));
+    }
+
+    #[test]
+    fn test_calculate_claim_fee_large_amount() {
+        let fee = u64::MAX / 10;
+        assert_eq!(calculate_claim_fee(u64::MAX, 1_000), Ok((fee, u64::MAX - fee)));
+    }
+}
@@ -1,15 +1,28 @@
-use luckycoin_api::{loaders::*, state::Proof};
+use luckycoin_api::{
+    cpi::emit_event,
+    error::LuckycoinError,
+    event::{CloseEvent, EventHeader, LuckycoinEvent},
+    loaders::*,
+    state::{MinerSet, Proof, Session},
+};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
-    system_program,
+    pubkey::Pubkey, system_program,
 };
 use steel::*;
 
+/// Close 关闭签名者的 proof 账户，并将租金退还给签名者。
+///
+/// proof 账户的收益和质押本金必须为零。与 proof 账户关联的 miner set 和 session 账户必须排在最后一并提供，
+/// 它们会和 proof 账户一起关闭。
 pub fn process_close(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
     let [
         signer, 
         proof_info, 
-        system_program
+        system_program,
+        event_authority_info,
+        luckycoin_program,
+        linked_infos @ ..
     ] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys); 
     };
@@ -21,14 +34,26 @@ pub fn process_close(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
         true
     )?;
     load_program(system_program, system_program::id())?;
+    load_event_authority(event_authority_info)?;
+    load_program(luckycoin_program, luckycoin_api::id())?;
 
     // 验证余额是否为零
-    let proof_data = proof_info.data.borrow();
-    let proof = Proof::try_from_bytes(&proof_data)?;
-    if proof.balance.gt(&0) {
+    let mut proof_data = proof_info.data.borrow_mut();
+    let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
+    if proof.earnings.gt(&0) || proof.stake.gt(&0) {
         return Err(ProgramError::InvalidAccountData); 
     }
 
+    // 关闭所有关联账户
+    close_linked_accounts(proof_info.key, proof, linked_infos, signer)?;
+    let event = CloseEvent {
+        header: EventHeader::new(LuckycoinEvent::Close),
+        authority: proof.authority,
+        proof: *proof_info.key,
+        total_hashes: proof.total_hashes,
+        total_rewards: proof.total_rewards,
+    };
+
     drop(proof_data);
 
     // 将证明账户的数据将重新分配为零
@@ -38,5 +63,166 @@ pub fn process_close(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
     **signer.lamports.borrow_mut() += proof_info.lamports();
     **proof_info.lamports.borrow_mut() = 0;
 
+    // 记录关闭事件
+    emit_event(event_authority_info, luckycoin_program, event.to_bytes())?;
+
+    Ok(())
+}
+
+/// 关闭给定的关联账户并将租金退还给 `recipient`，然后验证 proof 账户已经没有关联账户。
+///
+/// 关闭、回收和转移 proof 账户时都必须先关闭关联账户，否则它们会在 proof 账户被重新创建后重新生效。
+pub fn close_linked_accounts<'a>(
+    proof_address: &Pubkey,
+    proof: &mut Proof,
+    linked_infos: &[AccountInfo<'a>],
+    recipient: &AccountInfo<'a>,
+) -> ProgramResult {
+    for info in linked_infos {
+        if info.owner.ne(&luckycoin_api::id())
+            || !info.is_writable
+            || !is_linked_account(&info.data.borrow(), proof_address)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        close_account(info, recipient)?;
+        unlink_account(proof)?;
+    }
+    if proof.linked_accounts.gt(&0) {
+        return Err(LuckycoinError::LinkedAccountsOpen.into());
+    }
     Ok(())
 }
+
+/// 检查账户数据是否为属于给定 proof 账户的 miner set 或 session。
+pub fn is_linked_account(data: &[u8], proof_address: &Pubkey) -> bool {
+    match data.first() {
+        Some(d) if d.eq(&(MinerSet::discriminator() as u8)) => MinerSet::try_from_bytes(data)
+            .is_ok_and(|miner_set| miner_set.proof.eq(proof_address)),
+        Some(d) if d.eq(&(Session::discriminator() as u8)) => Session::try_from_bytes(data)
+            .is_ok_and(|session| session.proof.eq(proof_address)),
+        _ => false,
+    }
+}
+
+/// 从 proof 账户的关联账户数量中减去一个已关闭的账户。
+pub fn unlink_account(proof: &mut Proof) -> Result<(), LuckycoinError> {
+    proof.linked_accounts = proof
+        .linked_accounts
+        .checked_sub(1)
+        .ok_or(LuckycoinError::Overflow)?;
+    Ok(())
+}
+
+/// 将账户的数据重新分配为零，并将其 lamports 发送给 `recipient`。
+pub fn close_account<'a>(info: &AccountInfo<'a>, recipient: &AccountInfo<'a>) -> ProgramResult {
+    info.realloc(0, true)?;
+    **recipient.lamports.borrow_mut() += info.lamports();
+    **info.lamports.borrow_mut() = 0;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::size_of;
+
+    use luckycoin_api::{
+        error::LuckycoinError,
+        state::{MinerSet, Proof, Session},
+    };
+    use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+    use steel::*;
+
+    use super::{close_linked_accounts, is_linked_account, unlink_account};
+
+    fn miner_set_data(proof: Pubkey) -> Vec<u8> {
+        let mut data = vec![0u8; 8 + size_of::<MinerSet>()];
+        data[0] = MinerSet::discriminator() as u8;
+        MinerSet::try_from_bytes_mut(&mut data).unwrap().proof = proof;
+        data
+    }
+
+    fn session_data(proof: Pubkey) -> Vec<u8> {
+        let mut data = vec![0u8; 8 + size_of::<Session>()];
+        data[0] = Session::discriminator() as u8;
+        let session = Session::try_from_bytes_mut(&mut data).unwrap();
+        session.proof = proof;
+        session.key = Pubkey::new_unique();
+        data
+    }
+
+    #[test]
+    fn test_is_linked_account() {
+        let proof = Pubkey::new_unique();
+        assert!(is_linked_account(&miner_set_data(proof), &proof));
+        assert!(is_linked_account(&session_data(proof), &proof));
+        assert!(!is_linked_account(&session_data(Pubkey::new_unique()), &proof));
+
+        // 其他 proof 账户的 miner set
+        assert!(!is_linked_account(&miner_set_data(Pubkey::new_unique()), &proof));
+
+        // 其他类型的账户和已关闭的账户
+        let mut data = vec![0u8; 8 + size_of::<Proof>()];
+        data[0] = Proof::discriminator() as u8;
+        assert!(!is_linked_account(&data, &proof));
+        assert!(!is_linked_account(&[], &proof));
+    }
+
+    #[test]
+    fn test_unlink_account() {
+        let mut proof = Proof::zeroed();
+        proof.linked_accounts = 1;
+        assert_eq!(unlink_account(&mut proof), Ok(()));
+        assert_eq!(proof.linked_accounts, 0);
+        assert_eq!(unlink_account(&mut proof), Err(LuckycoinError::Overflow));
+    }
+
+    #[test]
+    fn test_close_linked_accounts_requires_all() {
+        let proof_address = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let owner = luckycoin_api::id();
+        let (mut recipient_lamports, mut miner_set_lamports) = (0, 0);
+        let mut recipient_data = vec![];
+        let recipient = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut recipient_lamports,
+            &mut recipient_data,
+            &owner,
+            false,
+            0,
+        );
+
+        // 没有关联账户时直接通过
+        let mut proof = Proof::zeroed();
+        assert_eq!(close_linked_accounts(&proof_address, &mut proof, &[], &recipient), Ok(()));
+
+        // 未提供的关联账户会阻止关闭
+        proof.linked_accounts = 1;
+        assert_eq!(
+            close_linked_accounts(&proof_address, &mut proof, &[], &recipient),
+            Err(LuckycoinError::LinkedAccountsOpen.into())
+        );
+
+        // 其他 proof 账户的 miner set 不能代替
+        let miner_set_key = Pubkey::new_unique();
+        let mut miner_set = miner_set_data(Pubkey::new_unique());
+        let other = AccountInfo::new(
+            &miner_set_key,
+            false,
+            true,
+            &mut miner_set_lamports,
+            &mut miner_set,
+            &owner,
+            false,
+            0,
+        );
+        assert_eq!(
+            close_linked_accounts(&proof_address, &mut proof, &[other], &recipient),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(proof.linked_accounts, 1);
+    }
+}
@@ -0,0 +1,30 @@
+use luckycoin_api::{instruction::CloseMinerSet, loaders::*, state::Proof};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+use steel::*;
+
+use crate::close::{close_account, unlink_account};
+
+/// CloseMinerSet 关闭签名者的 proof 账户的 miner set，撤销所有额外矿工密钥的授权，
+/// 并将租金退还给签名者。
+pub fn process_close_miner_set(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    CloseMinerSet::try_from_bytes(data)?;
+
+    // 加载账户
+    let [signer, miner_set_info, proof_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_proof(proof_info, signer.key, true)?;
+    load_miner_set(miner_set_info, proof_info.key, true)?;
+
+    // 关闭 miner set 并更新关联账户数量
+    close_account(miner_set_info, signer)?;
+    let mut proof_data = proof_info.data.borrow_mut();
+    let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
+    unlink_account(proof)?;
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use luckycoin_api::{loaders::*, state::Proof};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+use steel::*;
+
+use crate::close::{close_account, unlink_account};
+
+/// CloseSession 由 proof 授权者提前撤销会话，并将租金退还给签名者。过期的会话也可以用此指令关闭。
+pub fn process_close_session(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // 加载账户
+    let [signer, key_info, session_info, proof_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any(key_info, false)?;
+    load_proof(proof_info, signer.key, true)?;
+    load_session(session_info, proof_info.key, key_info.key, true)?;
+
+    // 关闭 session 账户并更新关联账户数量
+    close_account(session_info, signer)?;
+    let mut proof_data = proof_info.data.borrow_mut();
+    let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
+    unlink_account(proof)?;
+
+    Ok(())
+}
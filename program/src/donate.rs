@@ -0,0 +1,83 @@
+use luckycoin_api::{
+    consts::*,
+    cpi::{calculate_transfer_fee, emit_event, transfer_checked},
+    error::LuckycoinError,
+    event::{DonateEvent, EventHeader, LuckycoinEvent},
+    instruction::Donate,
+    loaders::*,
+    state::Config,
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+use steel::*;
+
+/// Donate 将代币转入国库并记入奖励池。达到最大供应量之后，奖励池用于继续支付挖矿奖励。
+pub fn process_donate(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    let args = Donate::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    let [
+        signer, 
+        config_info, 
+        sender_info, 
+        treasury_tokens_info, 
+        token_program,
+        mint_info,
+        event_authority_info,
+        luckycoin_program
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    // 加载配置账户
+    load_config(config_info, true)?;
+    // 加载发送者的代币账户，确保其有效性并与预定义的铸币地址相关联
+    load_token_account(
+        sender_info, 
+        Some(signer.key), 
+        &MINT_ADDRESS, 
+        true
+    )?;
+    // 加载国库代币账户
+    load_treasury_tokens(treasury_tokens_info, true)?;
+    // 加载铸币账户
+    load_mint(mint_info, MINT_ADDRESS, false)?;
+    // 加载代币程序，SPL Token 和 Token-2022 均可
+    load_token_program(token_program)?;
+    load_event_authority(event_authority_info)?;
+    load_program(luckycoin_program, luckycoin_api::id())?;
+
+    // 更新奖励池。如果铸币收取转账手续费，只记入国库实际收到的数量。
+    let received = amount
+        .checked_sub(calculate_transfer_fee(mint_info, amount)?)
+        .unwrap();
+    let mut config_data = config_info.data.borrow_mut();
+    let config = Config::try_from_bytes_mut(&mut config_data)?;
+    if config.is_paused(PAUSE_DONATE) {
+        return Err(LuckycoinError::Paused.into());
+    }
+    config.reward_pool = config.reward_pool.checked_add(received).unwrap();
+    let event = DonateEvent {
+        header: EventHeader::new(LuckycoinEvent::Donate),
+        donor: *signer.key,
+        amount,
+        received,
+        reward_pool: config.reward_pool,
+    };
+
+    // 将代币从签名者账户转移到国库代币账户
+    transfer_checked(
+        signer,
+        sender_info,
+        mint_info,
+        treasury_tokens_info,
+        token_program,
+        amount,
+    )?;
+
+    // 记录捐赠事件
+    emit_event(event_authority_info, luckycoin_program, event.to_bytes())?;
+
+    Ok(())
+}
@@ -1,9 +1,77 @@
-use solana_program::account_info::AccountInfo;
-use solana_program::entrypoint::ProgramResult;
-use solana_program::msg;
+use luckycoin_api::{
+    consts::*,
+    error::LuckycoinError,
+    loaders::*,
+    report::HealthReport,
+    state::{Bus, Config, Proof},
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program::set_return_data, program_error::ProgramError, sysvar::Sysvar,
+};
+use spl_token_2022::{extension::StateWithExtensions, state::Mint};
+use steel::*;
+
+/// Health 汇总协议和调用者 proof 账户的当前状态，并将报告写入返回数据。
+///
+/// 该指令不修改任何账户。所有 bus 账户都必须按顺序传入。
+pub fn process_health(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // 加载账户
+    let [signer, config_info, mint_info, proof_info, busses @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_config(config_info, false)?;
+    load_mint(mint_info, MINT_ADDRESS, false)?;
+    load_proof(proof_info, signer.key, false)?;
+
+    // 验证所有 bus 账户都已按顺序传入
+    let config_data = config_info.data.borrow();
+    let config = Config::try_from_bytes(&config_data)?;
+    if (busses.len() as u64).ne(&config.bus_count) {
+        return Err(LuckycoinError::BusCountInvalid.into());
+    }
+    let mut bus_rewards = [0u64; MAX_BUS_COUNT];
+    for (i, bus_info) in busses.iter().enumerate() {
+        load_bus(bus_info, i as u64, false)?;
+        let bus_data = bus_info.data.borrow();
+        let bus = Bus::try_from_bytes(&bus_data)?;
+        bus_rewards[i] = bus.rewards;
+    }
+
+    // 计算距离下一次重置的时间
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+    let t = clock.unix_timestamp;
+    let next_reset_at = config.last_reset_at.saturating_add(config.epoch_duration);
+    let seconds_until_reset = next_reset_at.saturating_sub(t).max(0) as u64;
+
+    // 计算距离下一次不被视为垃圾请求的提交的时间，与挖矿指令的检查一致
+    let proof_data = proof_info.data.borrow();
+    let proof = Proof::try_from_bytes(&proof_data)?;
+    let t_spam = proof
+        .last_hash_at
+        .saturating_add(ONE_MINUTE)
+        .saturating_sub(config.tolerance);
+    let seconds_until_submission = t_spam.saturating_sub(t).max(0) as u64;
+
+    // 读取当前供应量
+    let supply = StateWithExtensions::<Mint>::unpack(&mint_info.data.borrow())?
+        .base
+        .supply;
+
+    // 写入报告
+    let report = HealthReport {
+        seconds_until_reset,
+        reset_overdue: seconds_until_reset.eq(&0) as u64,
+        bus_count: config.bus_count,
+        bus_rewards,
+        min_difficulty: config.min_difficulty,
+        base_reward_rate: config.base_reward_rate,
+        supply,
+        max_supply: MAX_SUPPLY,
+        seconds_until_submission,
+    };
+    set_return_data(report.to_bytes());
 
-pub fn process_health(_accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
-    // 实现健康检查的逻辑
-    msg!("Health check processed.");
     Ok(())
-}
\ No newline at end of file
+}
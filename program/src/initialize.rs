@@ -2,12 +2,16 @@ use std::mem::size_of;
 
 use luckycoin_api::{
     consts::*,
+    error::LuckycoinError,
     instruction::*,
     loaders::*,
-    state::{Bus, Config, Treasury},
+    state::{Config, Treasury},
+};
+use solana_program::{self, account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError, pubkey::Pubkey, system_program, sysvar};
+use spl_token_2022::{
+    extension::{metadata_pointer, transfer_fee, ExtensionType},
+    state::Mint,
 };
-use solana_program::{self, account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError, program_pack::Pack, system_program, sysvar};
-use spl_token::state::Mint;
 use steel::*;
 use luckycoin_api::cpi::create_pda;
 use luckycoin_api::loaders::load_signer;
@@ -15,18 +19,14 @@ use luckycoin_api::loaders::load_signer;
 pub fn process_initialize(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // 解析传入的数据为 Initialize 结构体。
     let args = Initialize::try_from_bytes(data)?;
+    // 解析代币元数据。
+    let name = parse_metadata_string(&args.name, mpl_token_metadata::MAX_NAME_LENGTH)?;
+    let symbol = parse_metadata_string(&args.symbol, mpl_token_metadata::MAX_SYMBOL_LENGTH)?;
+    let uri = parse_metadata_string(&args.uri, mpl_token_metadata::MAX_URI_LENGTH)?;
     // 加载账户。
     // 将传入的账户解构为具体的变量。
     let [
         signer,
-        bus_0_info,
-        bus_1_info,
-        bus_2_info,
-        bus_3_info,
-        bus_4_info,
-        bus_5_info,
-        bus_6_info,
-        bus_7_info,
         config_info,
         metadata_info,
         mint_info,
@@ -44,55 +44,6 @@ pub fn process_initialize(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramR
     // 检查账户是否为签名者
     load_signer(signer)?;
 
-    // 加载未初始化的PDA(程序派生地址)以存储总线程数据
-    load_uninitialized_pda(
-        bus_0_info, 
-        &[BUS, &[0]], 
-        args.bus_0_bump, 
-        &luckycoin_api::id()
-    )?;
-    load_uninitialized_pda(
-        bus_1_info, 
-        &[BUS, &[1]], 
-        args.bus_1_bump, 
-        &luckycoin_api::id()
-    )?;
-    load_uninitialized_pda(
-        bus_2_info, 
-        &[BUS, &[2]],
-        args.bus_2_bump, 
-        &luckycoin_api::id()
-    )?;
-    load_uninitialized_pda(
-        bus_3_info, 
-        &[BUS, &[3]], 
-        args.bus_3_bump, 
-        &luckycoin_api::id()
-    )?;
-    load_uninitialized_pda(
-        bus_4_info, 
-        &[BUS, &[4]], 
-        args.bus_4_bump, 
-        &luckycoin_api::id()
-    )?;
-    load_uninitialized_pda(
-        bus_5_info, 
-        &[BUS, &[5]], 
-        args.bus_5_bump, 
-        &luckycoin_api::id()
-    )?;
-    load_uninitialized_pda(
-        bus_6_info, 
-        &[BUS, &[6]], 
-        args.bus_6_bump, 
-        &luckycoin_api::id()
-    )?;
-    load_uninitialized_pda(
-        bus_7_info, 
-        &[BUS, &[7]], 
-        args.bus_7_bump, 
-        &luckycoin_api::id()
-    )?;
     load_uninitialized_pda(
         config_info, 
         &[CONFIG], 
@@ -127,10 +78,13 @@ pub fn process_initialize(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramR
         system_program, 
         system_program::id()
     )?;
-    load_program(
-        token_program, 
-        spl_token::id()
-    )?;
+    // 代币程序可以是 SPL Token 或 Token-2022，铸币扩展仅 Token-2022 支持
+    load_token_program(token_program)?;
+    if args.extensions & !(MINT_EXTENSION_METADATA_POINTER | MINT_EXTENSION_TRANSFER_FEE) != 0
+        || (args.extensions.ne(&0) && token_program.key.ne(&spl_token_2022::id()))
+    {
+        return Err(LuckycoinError::MintExtensionsInvalid.into());
+    }
     load_program(
         associated_token_program, 
         spl_associated_token_account::id()
@@ -148,31 +102,6 @@ pub fn process_initialize(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramR
         return Err(ProgramError::MissingRequiredSignature); 
     }
 
-    let bus_infos = [
-        bus_0_info, bus_1_info, bus_2_info, bus_3_info, bus_4_info, bus_5_info, bus_6_info,
-        bus_7_info,
-    ];
-    let bus_bumps = [
-        args.bus_0_bump,
-        args.bus_1_bump,
-        args.bus_2_bump,
-        args.bus_3_bump,
-        args.bus_4_bump,
-        args.bus_5_bump,
-        args.bus_6_bump,
-        args.bus_7_bump,
-    ];
-
-    for i in 0..BUS_COUNT {
-        create_pda(bus_infos[i], &luckycoin_api::id(), 8 + size_of::<Bus>(), &[BUS, &[i as u8], &[bus_bumps[i]]], system_program, signer)?;
-        let mut bus_data = bus_infos[i].try_borrow_mut_data()?;
-        bus_data[0] = Bus::discriminator() as u8; /
-        let bus = Bus::try_from_bytes_mut(&mut bus_data)?;
-        bus.id = i as u64; 
-        bus.rewards = 0; 
-        bus.theoretical_rewards = 0; 
-        bus.top_balance = 0; 
-    }
     create_pda(
         config_info, 
         &luckycoin_api::id(), 
@@ -186,10 +115,7 @@ pub fn process_initialize(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramR
     let mut config_data = config_info.data.borrow_mut();
     config_data[0] = Config::discriminator() as u8; 
     let config = Config::try_from_bytes_mut(&mut config_data)?;
-    config.base_reward_rate = INITIAL_BASE_REWARD_RATE; 
-    config.last_reset_at = 0; 
-    config.min_difficulty = INITIAL_MIN_DIFFICULTY as u64; 
-    config.top_balance = 0; 
+    *config = initial_config(*signer.key);
 
     create_pda(
         treasury_info, 
@@ -201,21 +127,62 @@ pub fn process_initialize(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramR
 
     let mut treasury_data = treasury_info.data.borrow_mut();
     treasury_data[0] = Treasury::discriminator() as u8;
+    let treasury = Treasury::try_from_bytes_mut(&mut treasury_data)?;
+    treasury.total_credited = 0;
+    treasury.total_debited = 0;
     drop(treasury_data);
 
+    // 计算铸币账户的大小，包括所选的扩展
+    let mut extension_types = vec![];
+    if args.extensions & MINT_EXTENSION_METADATA_POINTER != 0 {
+        extension_types.push(ExtensionType::MetadataPointer);
+    }
+    if args.extensions & MINT_EXTENSION_TRANSFER_FEE != 0 {
+        extension_types.push(ExtensionType::TransferFeeConfig);
+    }
+    let mint_len = ExtensionType::try_calculate_account_len::<Mint>(&extension_types)?;
+
     create_pda(
         mint_info, 
-        &spl_token::id(), 
-        Mint::LEN, 
+        token_program.key, 
+        mint_len, 
         &[MINT, MINT_NOISE.as_slice(), 
         &[args.mint_bump]], 
         system_program, 
         signer
     )?;
 
+    // 扩展必须在铸币初始化之前初始化。元数据指针指向铸币的元数据账户，由财库管理。
+    if args.extensions & MINT_EXTENSION_METADATA_POINTER != 0 {
+        solana_program::program::invoke(
+            &metadata_pointer::instruction::initialize(
+                token_program.key,
+                mint_info.key,
+                Some(*treasury_info.key),
+                Some(*metadata_info.key),
+            )?,
+            &[token_program.clone(), mint_info.clone()],
+        )?;
+    }
+
+    // 转账手续费的配置和提取由初始化者管理。
+    if args.extensions & MINT_EXTENSION_TRANSFER_FEE != 0 {
+        solana_program::program::invoke(
+            &transfer_fee::instruction::initialize_transfer_fee_config(
+                token_program.key,
+                mint_info.key,
+                Some(signer.key),
+                Some(signer.key),
+                u16::from_le_bytes(args.transfer_fee_basis_points),
+                u64::from_le_bytes(args.maximum_fee),
+            )?,
+            &[token_program.clone(), mint_info.clone()],
+        )?;
+    }
+
     solana_program::program::invoke_signed(
-        &spl_token::instruction::initialize_mint(
-            &spl_token::id(), 
+        &spl_token_2022::instruction::initialize_mint(
+            token_program.key, 
             mint_info.key, 
             treasury_info.key, 
             None,
@@ -229,7 +196,7 @@ pub fn process_initialize(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramR
         &[&[MINT, MINT_NOISE.as_slice(), &[args.mint_bump]]],
     )?;
 
-    // 初始化铸造元数据。
+    // 初始化铸造元数据。更新授权者为财库，以便管理员之后通过 UpdateMetadata 指令修改元数据。
     msg!("load mpl_token_metadata account.....");
     mpl_token_metadata::instructions::CreateMetadataAccountV3Cpi {
         __program: metadata_program,
@@ -237,14 +204,14 @@ pub fn process_initialize(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramR
         mint: mint_info,
         mint_authority: treasury_info,
         payer: signer,
-        update_authority: (signer, true),
+        update_authority: (treasury_info, true),
         system_program,
         rent: Some(rent_sysvar),
         __args: mpl_token_metadata::instructions::CreateMetadataAccountV3InstructionArgs {
             data: mpl_token_metadata::types::DataV2 {
-                name: METADATA_NAME.to_string(),
-                symbol: METADATA_SYMBOL.to_string(),
-                uri: METADATA_URI.to_string(),
+                name,
+                symbol,
+                uri,
                 seller_fee_basis_points: 0,
                 creators: None,
                 collection: None,
@@ -255,15 +222,69 @@ pub fn process_initialize(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramR
         },
     }.invoke_signed(&[&[TREASURY, &[args.treasury_bump]]])?;
 
-    create_associated_token_account(
-        signer, 
-        treasury_info, 
-        treasury_tokens_info, 
-        mint_info, 
-        system_program, 
-        token_program, 
-        associated_token_program
+    // 在所选的代币程序下创建财库代币账户
+    solana_program::program::invoke(
+        &spl_associated_token_account::instruction::create_associated_token_account(
+            signer.key,
+            treasury_info.key,
+            mint_info.key,
+            token_program.key,
+        ),
+        &[
+            signer.clone(),
+            treasury_tokens_info.clone(),
+            treasury_info.clone(),
+            mint_info.clone(),
+            system_program.clone(),
+            token_program.clone(),
+            associated_token_program.clone(),
+        ],
     )?;
 
     Ok(())
 }
+
+/// 将零填充的字节数组解析为元数据字符串。
+/// 字符串必须是非空的有效 UTF-8，且长度不超过 `max_len`。
+pub(crate) fn parse_metadata_string(bytes: &[u8], max_len: usize) -> Result<String, ProgramError> {
+    let len = bytes.iter().rposition(|b| b.ne(&0)).map_or(0, |i| i + 1);
+    if len.eq(&0) || len.gt(&max_len) {
+        return Err(LuckycoinError::MetadataInvalid.into());
+    }
+    let s = std::str::from_utf8(&bytes[..len]).or(Err(LuckycoinError::MetadataInvalid))?;
+    Ok(s.to_string())
+}
+
+/// 程序初始化时的配置。MigrateConfig 也用它填充旧版本配置中缺失的字段。
+pub fn initial_config(admin: Pubkey) -> Config {
+    Config {
+        base_reward_rate: INITIAL_BASE_REWARD_RATE,
+        last_reset_at: 0,
+        min_difficulty: INITIAL_MIN_DIFFICULTY as u64,
+        top_balance: 0,
+        admin,
+        epoch_duration: EPOCH_DURATION,
+        tolerance: TOLERANCE,
+        base_reward_rate_min_threshold: BASE_REWARD_RATE_MIN_THRESHOLD,
+        base_reward_rate_max_threshold: BASE_REWARD_RATE_MAX_THRESHOLD,
+        smoothing_factor: SMOOTHING_FACTOR,
+        max_hash_reward: MAX_HASH_REWARD,
+        paused: 0,
+        unstake_cooldown: UNSTAKE_COOLDOWN,
+        jackpot_rate: JACKPOT_RATE,
+        jackpot_difficulty: JACKPOT_DIFFICULTY,
+        jackpot_odds: JACKPOT_ODDS,
+        tail_emission: TAIL_EMISSION,
+        reward_pool: 0,
+        emission_curve: EMISSION_CURVE,
+        emission_interval: EMISSION_INTERVAL,
+        emission_decay: EMISSION_DECAY,
+        epoch_count: 0,
+        reset_bounty: RESET_BOUNTY,
+        bus_count: 0,
+        auth_mode: AUTH_MODE,
+        claim_fee_bps: CLAIM_FEE_BPS,
+        reap_period: REAP_PERIOD,
+        pending_admin: Pubkey::default(),
+    }
+}
@@ -0,0 +1,62 @@
+use std::mem::size_of;
+
+use luckycoin_api::{
+    consts::*,
+    cpi::create_pda,
+    instruction::JoinPool,
+    loaders::*,
+    state::{Member, Pool},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    system_program,
+};
+use steel::*;
+
+/// JoinPool 为签名者在矿池中创建一个成员账户。成员按加入顺序获得编号，用于划分各自的随机数范围。
+pub fn process_join_pool(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = JoinPool::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer, pool_info, member_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any_pool(pool_info, true)?;
+    load_uninitialized_pda(
+        member_info,
+        &[MEMBER, pool_info.key.as_ref(), signer.key.as_ref()],
+        args.bump,
+        &luckycoin_api::id(),
+    )?;
+    load_program(system_program, system_program::id())?;
+
+    // Initialize member.
+    create_pda(
+        member_info,
+        &luckycoin_api::id(),
+        8 + size_of::<Member>(),
+        &[MEMBER, pool_info.key.as_ref(), signer.key.as_ref(), &[args.bump]],
+        system_program,
+        signer,
+    )?;
+    let mut pool_data = pool_info.data.borrow_mut();
+    let pool = Pool::try_from_bytes_mut(&mut pool_data)?;
+    let mut member_data = member_info.data.borrow_mut();
+    member_data[0] = Member::discriminator() as u8;
+    let member = Member::try_from_bytes_mut(&mut member_data)?;
+    member.authority = *signer.key;
+    member.pool = *pool_info.key;
+    member.balance = 0;
+    member.round = pool.round;
+    member.work = 0;
+    member.total_work = 0;
+    member.total_rewards = 0;
+    member.id = pool.total_members;
+
+    // 更新矿池的成员数量
+    pool.total_members = pool.total_members.saturating_add(1);
+
+    Ok(())
+}
@@ -1,23 +1,89 @@
+mod accept_admin;
+mod accept_authority;
+mod add_bus;
+mod add_miner;
+mod audit;
 mod claim;
 mod close;
+mod close_miner_set;
+mod close_session;
+mod donate;
 mod initialize;
+mod join_pool;
+mod lock;
+mod log;
+mod migrate;
+mod migrate_config;
+mod migrate_treasury;
 mod mine;
+mod mine_batch;
 mod open;
+mod open_pool;
+mod open_session;
+mod propose_admin;
+mod propose_authority;
+mod quote;
+mod reap;
+mod rebalance;
+mod remove_miner;
+mod pause;
+mod pool_claim;
+mod pool_mine;
+mod pool_settle;
+mod pool_submit;
 mod reset;
+mod set_config;
 mod stake;
+mod unstake;
+mod unpause;
+mod update_metadata;
 mod update;
 mod upgrade;
+mod withdraw_fees;
 mod health;
 
+use accept_admin::*;
+use accept_authority::*;
+use add_bus::*;
+use add_miner::*;
+use audit::*;
 use claim::*;
 use close::*;
+use close_miner_set::*;
+use close_session::*;
+use donate::*;
 use initialize::*;
+use join_pool::*;
+use lock::*;
+use log::*;
+use migrate::*;
+use migrate_config::*;
+use migrate_treasury::*;
 use mine::*;
+use mine_batch::*;
 use open::*;
+use open_pool::*;
+use open_session::*;
+use propose_admin::*;
+use propose_authority::*;
+use quote::*;
+use reap::*;
+use rebalance::*;
+use remove_miner::*;
+use pause::*;
+use pool_claim::*;
+use pool_mine::*;
+use pool_settle::*;
+use pool_submit::*;
 use reset::*;
+use set_config::*;
 use stake::*;
+use unstake::*;
+use unpause::*;
+use update_metadata::*;
 use update::*;
 use upgrade::*;
+use withdraw_fees::*;
 
 use luckycoin_api::instruction::*;
 use solana_program::{self, account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError, pubkey::Pubkey};
@@ -45,7 +111,40 @@ pub fn process_instruction(
         LuckycoinInstruction::Update => process_update(accounts, data)?,
         LuckycoinInstruction::Upgrade => process_upgrade(accounts, data)?,
         LuckycoinInstruction::Health => process_health(accounts, data)?,
+        LuckycoinInstruction::Unstake => process_unstake(accounts, data)?,
+        LuckycoinInstruction::Migrate => process_migrate(accounts, data)?,
+        LuckycoinInstruction::Lock => process_lock(accounts, data)?,
+        LuckycoinInstruction::ProposeAuthority => process_propose_authority(accounts, data)?,
+        LuckycoinInstruction::AcceptAuthority => process_accept_authority(accounts, data)?,
+        LuckycoinInstruction::OpenPool => process_open_pool(accounts, data)?,
+        LuckycoinInstruction::JoinPool => process_join_pool(accounts, data)?,
+        LuckycoinInstruction::PoolSubmit => process_pool_submit(accounts, data)?,
+        LuckycoinInstruction::PoolMine => process_pool_mine(accounts, data)?,
+        LuckycoinInstruction::PoolClaim => process_pool_claim(accounts, data)?,
+        LuckycoinInstruction::Log => process_log(accounts, data)?,
+        LuckycoinInstruction::Donate => process_donate(accounts, data)?,
+        LuckycoinInstruction::Rebalance => process_rebalance(accounts, data)?,
+        LuckycoinInstruction::AddMiner => process_add_miner(accounts, data)?,
+        LuckycoinInstruction::RemoveMiner => process_remove_miner(accounts, data)?,
+        LuckycoinInstruction::OpenSession => process_open_session(accounts, data)?,
+        LuckycoinInstruction::CloseSession => process_close_session(accounts, data)?,
+        LuckycoinInstruction::Audit => process_audit(accounts, data)?,
+        LuckycoinInstruction::Quote => process_quote(accounts, data)?,
+        LuckycoinInstruction::MineBatch => process_mine_batch(accounts, data)?,
+        LuckycoinInstruction::Reap => process_reap(accounts, data)?,
+        LuckycoinInstruction::PoolSettle => process_pool_settle(accounts, data)?,
+        LuckycoinInstruction::CloseMinerSet => process_close_miner_set(accounts, data)?,
         LuckycoinInstruction::Initialize => process_initialize(accounts, data)?,
+        LuckycoinInstruction::SetConfig => process_set_config(accounts, data)?,
+        LuckycoinInstruction::Pause => process_pause(accounts, data)?,
+        LuckycoinInstruction::Unpause => process_unpause(accounts, data)?,
+        LuckycoinInstruction::UpdateMetadata => process_update_metadata(accounts, data)?,
+        LuckycoinInstruction::AddBus => process_add_bus(accounts, data)?,
+        LuckycoinInstruction::WithdrawFees => process_withdraw_fees(accounts, data)?,
+        LuckycoinInstruction::MigrateConfig => process_migrate_config(accounts, data)?,
+        LuckycoinInstruction::ProposeAdmin => process_propose_admin(accounts, data)?,
+        LuckycoinInstruction::AcceptAdmin => process_accept_admin(accounts, data)?,
+        LuckycoinInstruction::MigrateTreasury => process_migrate_treasury(accounts, data)?,
     }
     Ok(())
 }
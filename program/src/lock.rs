@@ -0,0 +1,104 @@
+use luckycoin_api::{
+    consts::*,
+    error::LuckycoinError,
+    instruction::Lock,
+    loaders::*,
+    state::Proof,
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program_error::ProgramError, sysvar::Sysvar,
+};
+use steel::*;
+
+/// Lock 将矿工的质押锁定一个固定期限，以换取更高的质押奖励乘数。
+pub fn process_lock(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // 解析参数
+    let args = Lock::try_from_bytes(data)?;
+    let tier = args.tier as usize;
+
+    // 加载账户
+    let [signer, proof_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_proof(proof_info, signer.key, true)?;
+
+    // 更新锁定状态
+    let mut proof_data = proof_info.data.borrow_mut();
+    let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+    proof.lock_until = lock_until(proof, tier, clock.unix_timestamp)?;
+    proof.lock_tier = tier as u64;
+
+    Ok(())
+}
+
+/// 计算在时间 `t` 选择锁定档位 `tier` 之后的锁定到期时间。
+///
+/// 档位 0 表示不锁定，不能主动选择。正在生效的锁定只能延长或升级，不能缩短或降级。
+pub fn lock_until(proof: &Proof, tier: usize, t: i64) -> Result<i64, ProgramError> {
+    // 验证锁定档位
+    if tier.eq(&0) || tier.ge(&LOCK_TIER_COUNT) {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // 正在生效的锁定只能延长或升级
+    let lock_until = t.saturating_add(LOCK_DURATIONS[tier]);
+    if proof.lock_until.gt(&t)
+        && (lock_until.lt(&proof.lock_until) || (tier as u64).lt(&proof.lock_tier))
+    {
+        return Err(LuckycoinError::LockInvalid.into());
+    }
+
+    Ok(lock_until)
+}
+
+#[cfg(test)]
+mod tests {
+    use luckycoin_api::{
+        consts::{LOCK_DURATIONS, LOCK_TIER_COUNT, ONE_WEEK},
+        error::LuckycoinError,
+        state::Proof,
+    };
+    use solana_program::program_error::ProgramError;
+    use steel::*;
+
+    use super::lock_until;
+
+    #[test]
+    fn test_lock_until_tiers() {
+        let proof = Proof::zeroed();
+        for (tier, duration) in LOCK_DURATIONS.iter().enumerate().skip(1) {
+            assert_eq!(lock_until(&proof, tier, 100), Ok(100 + duration));
+        }
+    }
+
+    #[test]
+    fn test_lock_until_invalid_tier() {
+        let proof = Proof::zeroed();
+        assert_eq!(lock_until(&proof, 0, 100), Err(ProgramError::InvalidInstructionData));
+        assert_eq!(
+            lock_until(&proof, LOCK_TIER_COUNT, 100),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_lock_until_active_lock() {
+        let mut proof = Proof::zeroed();
+        proof.lock_tier = 2;
+        proof.lock_until = ONE_WEEK * 4;
+
+        // 不能降级或缩短正在生效的锁定
+        assert_eq!(lock_until(&proof, 1, 0), Err(LuckycoinError::LockInvalid.into()));
+        assert_eq!(lock_until(&proof, 2, -1), Err(LuckycoinError::LockInvalid.into()));
+
+        // 可以延长或升级
+        assert_eq!(lock_until(&proof, 2, 1), Ok(ONE_WEEK * 4 + 1));
+        assert_eq!(lock_until(&proof, 3, 0), Ok(ONE_WEEK * 12));
+
+        // 锁定到期之后可以重新选择任意档位
+        assert_eq!(lock_until(&proof, 1, ONE_WEEK * 4), Ok(ONE_WEEK * 5));
+    }
+}
@@ -0,0 +1,18 @@
+use luckycoin_api::loaders::*;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+/// Log 是一个空操作指令，程序通过 CPI 调用它来记录事件。
+///
+/// 事件数据保存在内部指令的数据中，供索引器解析。只有程序自身能以事件授权者签名，
+/// 因此外部无法伪造事件。
+pub fn process_log(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    let [event_authority_info, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(event_authority_info)?;
+    load_event_authority(event_authority_info)?;
+
+    Ok(())
+}
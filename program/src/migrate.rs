@@ -0,0 +1,167 @@
+use std::mem::size_of;
+
+use luckycoin_api::{
+    loaders::*,
+    state::{Bus, Proof},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    rent::Rent, system_program, sysvar::Sysvar,
+};
+use steel::*;
+
+/// 拆分余额之前的 proof 账户数据长度：判别符之后依次是授权者、余额、挑战、最近的哈希、
+/// 最近的哈希时间、最近的质押时间、矿工、总哈希数和总奖励。
+const LEGACY_PROOF_LEN: usize = 8 + 32 + 8 + 32 + 32 + 8 + 8 + 32 + 8 + 8;
+
+/// Migrate 将旧版本的 proof 或 bus 账户扩容到当前的数据布局，任何人都可以调用。
+///
+/// 新增的字段始终追加在结构体末尾，并以零值初始化。旧版本的 `balance` 混合了挖矿收益和质押本金，
+/// 迁移时按 [`split_legacy_balance`] 拆分：挖矿收益仍可直接领取，质押本金只能通过解除质押取回。
+/// bus 账户新增的幸运奖池和负债从零开始累积。
+pub fn process_migrate(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer, account_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_program(system_program, system_program::id())?;
+
+    // 旧版本的账户无法按当前布局反序列化，因此只检查所有者和判别符
+    if load_any_proof(account_info, true).is_ok() {
+        let new_len = 8 + size_of::<Proof>();
+        let old_len = realloc_account(signer, account_info, system_program, new_len)?;
+        migrate_proof(&mut account_info.data.borrow_mut(), old_len)?;
+    } else {
+        load_any_bus(account_info, true)?;
+        realloc_account(signer, account_info, system_program, 8 + size_of::<Bus>())?;
+    }
+
+    Ok(())
+}
+
+/// 迁移扩容后的 proof 账户数据。拆分余额之前的账户将原有余额拆分为挖矿收益和质押本金。
+pub fn migrate_proof(data: &mut [u8], old_len: usize) -> Result<(), ProgramError> {
+    if old_len.eq(&LEGACY_PROOF_LEN) {
+        let proof = Proof::try_from_bytes_mut(data)?;
+        (proof.earnings, proof.stake) = split_legacy_balance(proof.earnings, proof.total_rewards);
+    }
+    Ok(())
+}
+
+/// 将旧版本的余额拆分为挖矿收益和质押本金。
+///
+/// 旧版本没有记录余额的来源，也无法区分已领取的部分。余额中不超过累计挖矿奖励的部分视为收益，
+/// 其余部分只可能来自质押。这样用户挖到的奖励始终可以直接领取，不会被锁定为质押本金。
+pub fn split_legacy_balance(balance: u64, total_rewards: u64) -> (u64, u64) {
+    let earnings = balance.min(total_rewards);
+    (earnings, balance.saturating_sub(earnings))
+}
+
+/// 将账户扩容到 `new_len` 字节，并由签名者补足所需的租金。新增的数据以零值初始化。
+///
+/// 返回扩容前的数据长度。账户已经不小于 `new_len` 时不做任何修改。
+pub fn realloc_account<'a>(
+    signer: &AccountInfo<'a>,
+    info: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    new_len: usize,
+) -> Result<usize, ProgramError> {
+    // 已经是当前布局，无需迁移
+    let old_len = info.data_len();
+    if old_len.ge(&new_len) {
+        return Ok(old_len);
+    }
+
+    // 补足扩容后所需的租金
+    let rent = Rent::get()?;
+    let lamports = rent.minimum_balance(new_len).saturating_sub(info.lamports());
+    if lamports.gt(&0) {
+        solana_program::program::invoke(
+            &solana_program::system_instruction::transfer(signer.key, info.key, lamports),
+            &[signer.clone(), info.clone(), system_program.clone()],
+        )?;
+    }
+
+    // 扩容账户
+    info.realloc(new_len, true)?;
+
+    Ok(old_len)
+}
+
+/// 用默认值填充扩容后追加的字段。
+///
+/// `data` 是包含判别符的完整账户数据，`defaults` 是不含判别符的默认账户状态，`old_len`
+/// 是扩容前的数据长度。扩容前已经存在的字段保持不变。
+pub fn fill_appended_fields(data: &mut [u8], old_len: usize, defaults: &[u8]) {
+    if old_len.lt(&data.len()) {
+        data[old_len..].copy_from_slice(&defaults[old_len.saturating_sub(8)..]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::size_of;
+
+    use luckycoin_api::state::Proof;
+    use solana_program::pubkey::Pubkey;
+    use steel::*;
+
+    use super::{migrate_proof, split_legacy_balance, LEGACY_PROOF_LEN};
+
+    #[test]
+    fn test_migrate_legacy_proof() {
+        // 旧版本的余额位于授权者之后。没有累计挖矿奖励时余额全部来自质押
+        let authority = Pubkey::new_unique();
+        let mut data = vec![0u8; LEGACY_PROOF_LEN];
+        data[0] = Proof::discriminator() as u8;
+        data[8..40].copy_from_slice(authority.as_ref());
+        data[40..48].copy_from_slice(&1_000u64.to_le_bytes());
+        data.resize(8 + size_of::<Proof>(), 0);
+        migrate_proof(&mut data, LEGACY_PROOF_LEN).unwrap();
+        let proof = Proof::try_from_bytes(&data).unwrap();
+        assert_eq!(proof.authority, authority);
+        assert_eq!(proof.stake, 1_000);
+        assert_eq!(proof.earnings, 0);
+        assert_eq!(proof.lock_until, 0);
+        assert_eq!(proof.pending_authority, Pubkey::default());
+    }
+
+    #[test]
+    fn test_migrate_current_proof() {
+        // 已经拆分余额的账户保持不变
+        let mut data = vec![0u8; 8 + size_of::<Proof>()];
+        data[0] = Proof::discriminator() as u8;
+        let proof = Proof::try_from_bytes_mut(&mut data).unwrap();
+        proof.earnings = 100;
+        proof.stake = 200;
+        migrate_proof(&mut data, 8 + size_of::<Proof>()).unwrap();
+        let proof = Proof::try_from_bytes(&data).unwrap();
+        assert_eq!(proof.earnings, 100);
+        assert_eq!(proof.stake, 200);
+    }
+
+    #[test]
+    fn test_migrate_legacy_proof_with_earnings_and_stake() {
+        // 旧版本的余额中既有挖矿收益也有质押本金，累计奖励位于总哈希数之后
+        let mut data = vec![0u8; LEGACY_PROOF_LEN];
+        data[0] = Proof::discriminator() as u8;
+        data[40..48].copy_from_slice(&1_500u64.to_le_bytes());
+        data[168..176].copy_from_slice(&1_000u64.to_le_bytes());
+        data.resize(8 + size_of::<Proof>(), 0);
+        migrate_proof(&mut data, LEGACY_PROOF_LEN).unwrap();
+        let proof = Proof::try_from_bytes(&data).unwrap();
+        assert_eq!(proof.earnings, 1_000);
+        assert_eq!(proof.stake, 500);
+        assert_eq!(proof.total_rewards, 1_000);
+    }
+
+    #[test]
+    fn test_split_legacy_balance() {
+        assert_eq!(split_legacy_balance(1_500, 1_000), (1_000, 500));
+        // 已经领取过部分奖励时，剩余余额全部视为收益
+        assert_eq!(split_legacy_balance(800, 1_000), (800, 0));
+        assert_eq!(split_legacy_balance(0, 1_000), (0, 0));
+        assert_eq!(split_legacy_balance(1_000, 0), (0, 1_000));
+    }
+}
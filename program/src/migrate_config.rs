@@ -0,0 +1,120 @@
+use std::mem::size_of;
+
+use luckycoin_api::{consts::*, loaders::*, state::Config};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    system_program,
+};
+use steel::*;
+
+use crate::{
+    initialize::initial_config,
+    migrate::{fill_appended_fields, realloc_account},
+};
+
+/// MigrateConfig 将旧版本的配置账户扩容到当前的数据布局。
+///
+/// 配置的新字段始终追加在结构体末尾，迁移时以初始化时的默认值填充，已有字段保持不变。旧版本的配置
+/// 没有管理员字段，此时只有初始化者可以迁移，迁移后管理员即为初始化者。迁移后 bus 数量为零，
+/// 已经存在的 bus 账户需要通过 AddBus 按编号依次接管。
+pub fn process_migrate_config(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer, config_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    // 旧版本的账户无法按当前布局反序列化，因此只检查地址、所有者和判别符
+    load_config(config_info, true)?;
+    load_program(system_program, system_program::id())?;
+
+    // 验证签名者是管理员。没有管理员字段的旧配置由初始化者迁移。
+    let admin =
+        Config::admin_from_bytes(&config_info.data.borrow()).unwrap_or(INITIALIZER_ADDRESS);
+    if signer.key.ne(&admin) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // 扩容账户，并用默认值填充新增字段
+    let old_len = realloc_account(signer, config_info, system_program, 8 + size_of::<Config>())?;
+    let defaults = initial_config(INITIALIZER_ADDRESS);
+    fill_appended_fields(&mut config_info.data.borrow_mut(), old_len, defaults.to_bytes());
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::mem::size_of;
+
+    use luckycoin_api::{consts::*, state::Config};
+    use solana_program::pubkey::Pubkey;
+    use steel::*;
+
+    use crate::{initialize::initial_config, migrate::fill_appended_fields};
+
+    // 管理员字段出现之前的配置只有四个 u64 字段
+    const LEGACY_CONFIG_LEN: usize = 8 + 4 * size_of::<u64>();
+
+    fn legacy_config() -> Vec<u8> {
+        let mut data = vec![0u8; LEGACY_CONFIG_LEN];
+        data[0] = Config::discriminator() as u8;
+        data[8..16].copy_from_slice(&42u64.to_le_bytes());
+        data[16..24].copy_from_slice(&1_700_000_000i64.to_le_bytes());
+        data[24..32].copy_from_slice(&12u64.to_le_bytes());
+        data[32..40].copy_from_slice(&1_000u64.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_admin_from_bytes() {
+        assert_eq!(Config::admin_from_bytes(&legacy_config()), None);
+        let admin = Pubkey::new_unique();
+        let mut data = vec![0u8; 8 + size_of::<Config>()];
+        data[0] = Config::discriminator() as u8;
+        Config::try_from_bytes_mut(&mut data).unwrap().admin = admin;
+        assert_eq!(Config::admin_from_bytes(&data), Some(admin));
+    }
+
+    #[test]
+    fn test_migrate_legacy_config() {
+        let mut data = legacy_config();
+        data.resize(8 + size_of::<Config>(), 0);
+        let defaults = initial_config(INITIALIZER_ADDRESS);
+        fill_appended_fields(&mut data, LEGACY_CONFIG_LEN, defaults.to_bytes());
+        let config = Config::try_from_bytes(&data).unwrap();
+
+        // 已有字段保持不变
+        assert_eq!(config.base_reward_rate, 42);
+        assert_eq!(config.last_reset_at, 1_700_000_000);
+        assert_eq!(config.min_difficulty, 12);
+        assert_eq!(config.top_balance, 1_000);
+
+        // 新增字段使用初始化时的默认值
+        assert_eq!(config.admin, INITIALIZER_ADDRESS);
+        assert_eq!(config.epoch_duration, EPOCH_DURATION);
+        assert_eq!(config.max_hash_reward, MAX_HASH_REWARD);
+        assert_eq!(config.reap_period, REAP_PERIOD);
+        assert_eq!(config.bus_count, 0);
+        assert_eq!(config.pending_admin, Pubkey::default());
+    }
+
+    #[test]
+    fn test_migrate_partial_config() {
+        // 只缺少最后一个字段的配置保留全部已有设置
+        let mut config = initial_config(Pubkey::new_unique());
+        config.epoch_duration = ONE_MINUTE * 5;
+        config.paused = PAUSE_MINE;
+        config.pending_admin = Pubkey::new_unique();
+        let mut data = vec![Config::discriminator() as u8, 0, 0, 0, 0, 0, 0, 0];
+        data.extend_from_slice(config.to_bytes());
+        let old_len = data.len() - size_of::<Pubkey>();
+        let defaults = initial_config(INITIALIZER_ADDRESS);
+        fill_appended_fields(&mut data, old_len, defaults.to_bytes());
+        let migrated = Config::try_from_bytes(&data).unwrap();
+        assert_eq!(migrated.admin, config.admin);
+        assert_eq!(migrated.epoch_duration, ONE_MINUTE * 5);
+        assert_eq!(migrated.paused, PAUSE_MINE);
+        assert_eq!(migrated.pending_admin, Pubkey::default());
+    }
+}
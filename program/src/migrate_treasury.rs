@@ -0,0 +1,175 @@
+use std::mem::size_of;
+
+use luckycoin_api::{
+    error::LuckycoinError,
+    loaders::*,
+    state::{Bus, Config, Treasury},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    system_program,
+};
+use spl_token_2022::{extension::StateWithExtensions, state::Account};
+use steel::*;
+
+use crate::migrate::{fill_appended_fields, realloc_account};
+
+/// MigrateTreasury 将旧版本的财库账户扩容到当前的数据布局。
+///
+/// 旧版本的财库只有判别符，没有负债记录。迁移时以财库代币账户的余额减去尚未挖出的代币作为累计
+/// 记入的总额。尚未挖出的代币包括 bus 剩余的奖励、幸运奖池和奖励池，它们挖出时才记入负债；
+/// bus 中尚未结算的负债在下次重置时结算，也不计入。所有 bus 账户都必须按顺序传入。仅管理员可调用。
+pub fn process_migrate_treasury(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // 加载账户
+    let [
+        signer,
+        config_info,
+        treasury_info,
+        treasury_tokens_info,
+        system_program,
+        busses @ ..
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_config_with_admin(config_info, signer.key, false)?;
+    // 旧版本的账户无法按当前布局反序列化，因此只检查地址、所有者和判别符
+    load_treasury(treasury_info, true)?;
+    load_treasury_tokens(treasury_tokens_info, false)?;
+    load_program(system_program, system_program::id())?;
+
+    // 验证所有 bus 账户都已按顺序传入
+    let config_data = config_info.data.borrow();
+    let config = Config::try_from_bytes(&config_data)?;
+    if (busses.len() as u64).ne(&config.bus_count) {
+        return Err(LuckycoinError::BusCountInvalid.into());
+    }
+    let mut bus_states = Vec::with_capacity(busses.len());
+    for (i, bus_info) in busses.iter().enumerate() {
+        load_bus(bus_info, i as u64, false)?;
+        bus_states.push(*Bus::try_from_bytes(&bus_info.data.borrow())?);
+    }
+
+    // 扩容账户，并以财库代币余额中已挖出的部分填充负债记录
+    let balance = StateWithExtensions::<Account>::unpack(&treasury_tokens_info.data.borrow())?
+        .base
+        .amount;
+    let defaults = initial_treasury(balance, &bus_states, config.reward_pool)?;
+    let old_len =
+        realloc_account(signer, treasury_info, system_program, 8 + size_of::<Treasury>())?;
+    fill_appended_fields(&mut treasury_info.data.borrow_mut(), old_len, defaults.to_bytes());
+
+    Ok(())
+}
+
+/// 迁移时使用的财库状态。
+///
+/// 财库代币余额减去 bus 剩余的奖励、幸运奖池、尚未结算的负债和奖励池后，记为已记入 proof 账户的代币。
+pub fn initial_treasury(
+    balance: u64,
+    busses: &[Bus],
+    reward_pool: u64,
+) -> Result<Treasury, LuckycoinError> {
+    let mut total_credited = balance
+        .checked_sub(reward_pool)
+        .ok_or(LuckycoinError::Overflow)?;
+    for bus in busses {
+        total_credited = total_credited
+            .checked_sub(bus.rewards)
+            .and_then(|v| v.checked_sub(bus.jackpot))
+            .and_then(|v| v.checked_sub(bus.liabilities))
+            .ok_or(LuckycoinError::Overflow)?;
+    }
+    Ok(Treasury {
+        total_credited,
+        total_debited: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::size_of;
+
+    use luckycoin_api::{
+        error::LuckycoinError,
+        report::SolvencyReport,
+        state::{Bus, Treasury},
+    };
+    use steel::*;
+
+    use super::initial_treasury;
+    use crate::migrate::fill_appended_fields;
+
+    // 负债记录出现之前的财库只有判别符
+    const LEGACY_TREASURY_LEN: usize = 8;
+
+    fn legacy_treasury() -> Vec<u8> {
+        let mut data = vec![0u8; LEGACY_TREASURY_LEN];
+        data[0] = Treasury::discriminator() as u8;
+        data
+    }
+
+    #[test]
+    fn test_migrate_legacy_treasury() {
+        let mut data = legacy_treasury();
+        data.resize(8 + size_of::<Treasury>(), 0);
+        let defaults = initial_treasury(5_000, &[], 0).unwrap();
+        fill_appended_fields(&mut data, LEGACY_TREASURY_LEN, defaults.to_bytes());
+        let treasury = Treasury::try_from_bytes(&data).unwrap();
+        assert_eq!(treasury.total_credited, 5_000);
+        assert_eq!(treasury.total_debited, 0);
+        assert_eq!(treasury.liabilities(0), 5_000);
+        assert_eq!(treasury.liabilities(100), 5_100);
+    }
+
+    #[test]
+    fn test_migrate_current_treasury() {
+        // 已经是当前布局的财库保留原有负债记录
+        let current = Treasury {
+            total_credited: 9_000,
+            total_debited: 4_000,
+        };
+        let mut data = vec![Treasury::discriminator() as u8, 0, 0, 0, 0, 0, 0, 0];
+        data.extend_from_slice(current.to_bytes());
+        let old_len = data.len();
+        let defaults = initial_treasury(1, &[], 0).unwrap();
+        fill_appended_fields(&mut data, old_len, defaults.to_bytes());
+        assert_eq!(*Treasury::try_from_bytes(&data).unwrap(), current);
+    }
+
+    #[test]
+    fn test_migrate_treasury_with_unmined_rewards() {
+        // 迁移时 bus 仍有剩余奖励、幸运奖池和尚未结算的负债
+        let mut busses = [Bus::zeroed(), Bus::zeroed()];
+        busses[0].rewards = 1_000;
+        busses[0].jackpot = 200;
+        busses[0].liabilities = 300;
+        busses[1].rewards = 500;
+        busses[1].jackpot = 100;
+        let reward_pool = 400;
+        let balance = 10_000;
+        let treasury = initial_treasury(balance, &busses, reward_pool).unwrap();
+        assert_eq!(treasury.total_credited, 7_500);
+
+        // 审计报告偿付能力充足，盈余恰好是尚未挖出的代币
+        let pending = busses.iter().map(|bus| bus.liabilities).sum();
+        let report = SolvencyReport::new(treasury.liabilities(pending), pending, balance);
+        assert!(report.is_solvent());
+        assert_eq!(report.surplus, 2_200);
+
+        // 剩余奖励全部挖出并结算后仍然偿付充足
+        let mined = 1_000 + 200 + 500 + 100;
+        let report = SolvencyReport::new(treasury.liabilities(pending + mined), pending, balance);
+        assert!(report.is_solvent());
+        assert_eq!(report.surplus, reward_pool);
+    }
+
+    #[test]
+    fn test_migrate_treasury_underfunded() {
+        let mut bus = Bus::zeroed();
+        bus.rewards = 1_000;
+        assert_eq!(initial_treasury(999, &[bus], 0), Err(LuckycoinError::Overflow));
+        assert_eq!(initial_treasury(1_000, &[bus], 1), Err(LuckycoinError::Overflow));
+    }
+}
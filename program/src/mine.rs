@@ -3,17 +3,20 @@ use std::mem::size_of;
 use drillx::Solution;
 use luckycoin_api::{
     consts::*,
+    cpi::emit_event,
     error::LuckycoinError,
-    event::MineEvent,
+    event::{EventHeader, JackpotEvent, LuckycoinEvent, MineEvent},
     instruction::Mine,
     loaders::*,
-    state::{Bus, Config, Proof},
+    reward::{calculate_reward, Reward},
+    state::{Bus, Config, MinerSet, Proof, Session},
 };
-use solana_program::{clock, program::set_return_data};
+use solana_program::clock;
 #[allow(deprecated)]
 use solana_program::{
     account_info::AccountInfo,
     clock::Clock,
+    ed25519_program,
     entrypoint::ProgramResult,
     keccak::hashv,
     program_error::ProgramError,
@@ -33,7 +36,10 @@ pub fn process_mine(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
         config_info, 
         proof_info, 
         instructions_sysvar, 
-        slot_hashes_sysvar
+        slot_hashes_sysvar,
+        event_authority_info,
+        luckycoin_program,
+        remaining_accounts @ ..
     ] = accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys); 
@@ -41,37 +47,164 @@ pub fn process_mine(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
     load_signer(signer)?; 
     load_any_bus(bus_info, true)?; 
     load_config(config_info, false)?; 
-    load_proof_with_miner(proof_info, signer.key, true)?; 
+    load_any_proof(proof_info, true)?; 
     load_sysvar(instructions_sysvar, sysvar::instructions::id())?; 
     load_sysvar(slot_hashes_sysvar, sysvar::slot_hashes::id())?; 
+    load_event_authority(event_authority_info)?;
+    load_program(luckycoin_program, luckycoin_api::id())?;
 
-    // 认证证明账户
-    authenticate(&instructions_sysvar.data.borrow(), proof_info.key)?;
+    // 剩余账户中可以包含 proof 账户的 miner set、签名者的会话和备用 bus，按账户类型区分
+    let mut miner_set_info = None;
+    let mut session_info = None;
+    let mut fallback_busses = Vec::with_capacity(remaining_accounts.len());
+    for info in remaining_accounts.iter() {
+        let discriminator = info.data.borrow().first().copied();
+        if discriminator.eq(&Some(MinerSet::discriminator() as u8)) {
+            load_miner_set(info, proof_info.key, false)?;
+            miner_set_info = Some(info);
+        } else if discriminator.eq(&Some(Session::discriminator() as u8)) {
+            load_session(info, proof_info.key, signer.key, false)?;
+            session_info = Some(info);
+        } else {
+            load_any_bus(info, true)?;
+            fallback_busses.push(info);
+        }
+    }
 
-    // 验证当前纪元是否有效，确保挖矿操作在合适的时间窗口内有效进行
-    // 检查当前时间是否已超过上次重置时间加上设定的纪元持续时间
+    // 检查挖矿是否已被暂停
     let config_data = config_info.data.borrow();
     let config = Config::try_from_bytes(&config_data)?;
+    if config.is_paused(PAUSE_MINE) {
+        return Err(LuckycoinError::Paused.into());
+    }
+
+    // 如果选择的 bus 已耗尽，则从第一个仍有奖励的备用 bus 中支付
+    let bus_info = select_bus(bus_info, &fallback_busses)?;
+
+    // 处理解决方案
     let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
-    if config.last_reset_at.saturating_add(EPOCH_DURATION).le(&clock.unix_timestamp){
+    let mut bus_data = bus_info.data.borrow_mut();
+    let bus = Bus::try_from_bytes_mut(&mut bus_data)?;
+    let mut proof_data = proof_info.data.borrow_mut();
+    let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
+
+    // 验证签名者是 proof 的矿工、miner set 中未过期的授权矿工，或是有效会话的会话密钥
+    let miner_set_data = miner_set_info.map(|info| info.data.borrow());
+    let miner_set = miner_set_data
+        .as_deref()
+        .map(|data| MinerSet::try_from_bytes(data))
+        .transpose()?;
+    let session_data = session_info.map(|info| info.data.borrow());
+    let session = session_data
+        .as_deref()
+        .map(|data| Session::try_from_bytes(data))
+        .transpose()?;
+    authorize_miner(proof, signer.key, miner_set, session, clock.slot, clock.unix_timestamp)?;
+
+    // 认证证明账户。签名者已经被授权挖矿，因此授权者和签名者的签名都被接受。
+    authenticate(
+        &instructions_sysvar.data.borrow(),
+        config.auth_mode,
+        proof_info.key,
+        &[proof.authority, *signer.key],
+        &proof.challenge,
+    )?;
+
+    let result = mine_solution(
+        config,
+        bus,
+        proof,
+        Solution::new(args.digest, args.nonce),
+        &slot_hashes_sysvar.data.borrow(),
+        clock.slot,
+        clock.unix_timestamp,
+    )?;
+
+    // 记录幸运奖池的支付
+    if result.jackpot.gt(&0) {
+        let event = JackpotEvent {
+            header: EventHeader::new(LuckycoinEvent::Jackpot),
+            authority: proof.authority,
+            bus: bus.id,
+            difficulty: result.difficulty as u64,
+            amount: result.jackpot,
+            timing: clock.unix_timestamp,
+        };
+        emit_event(event_authority_info, luckycoin_program, event.to_bytes())?;
+    }
+
+    // 记录挖矿奖励
+    //
+    // This data can be used by off-chain indexers to display mining stats.
+    let event = MineEvent {
+        header: EventHeader::new(LuckycoinEvent::Mine),
+        difficulty: result.difficulty as u64,
+        reward: result.reward_actual,
+        timing: result.timing,
+    };
+    emit_event(event_authority_info, luckycoin_program, event.to_bytes())?;
+
+    Ok(())
+}
+
+/// 按顺序返回第一个仍有奖励的 bus。如果所有 bus 都已耗尽，则返回选择的 bus。
+fn select_bus<'a, 'info>(
+    bus_info: &'a AccountInfo<'info>,
+    fallback_busses: &[&'a AccountInfo<'info>],
+) -> Result<&'a AccountInfo<'info>, ProgramError> {
+    for info in std::iter::once(bus_info).chain(fallback_busses.iter().copied()) {
+        let bus_data = info.data.borrow();
+        let bus = Bus::try_from_bytes(&bus_data)?;
+        if bus.rewards.gt(&0) {
+            return Ok(info);
+        }
+    }
+    Ok(bus_info)
+}
+
+/// 一次成功挖矿的结果。
+pub(crate) struct MineResult {
+    /// 解决方案的难度。
+    pub difficulty: u32,
+
+    /// 实际记入 proof 账户的奖励，不含幸运奖池。
+    pub reward_actual: u64,
+
+    /// 赢得的幸运奖池金额。未中奖时为 0。
+    pub jackpot: u64,
+
+    /// 提交时间相对于活跃度截止时间的偏移（秒）。
+    pub timing: i64,
+}
+
+/// 验证解决方案并更新 bus 和 proof 账户。单个矿工的挖矿和矿池的挖矿共享此逻辑。
+pub(crate) fn mine_solution(
+    config: &Config,
+    bus: &mut Bus,
+    proof: &mut Proof,
+    solution: Solution,
+    slot_hashes: &[u8],
+    slot: u64,
+    t: i64,
+) -> Result<MineResult, ProgramError> {
+    // 验证当前纪元是否有效，确保挖矿操作在合适的时间窗口内有效进行
+    // 检查当前时间是否已超过上次重置时间加上设定的纪元持续时间
+    if config.last_reset_at.saturating_add(config.epoch_duration).le(&t){
         return Err(LuckycoinError::NeedsReset.into()); 
     }
 
     // 验证哈希摘要(解析出证明信息，创建解决方案并验证是否符合挑战条件)
-    let mut proof_data = proof_info.data.borrow_mut();
-    let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
-    let solution = Solution::new(args.digest, args.nonce);
     if !solution.is_valid(&proof.challenge) {
         return Err(LuckycoinError::HashInvalid.into()); 
     }
 
     // 拒绝垃圾邮件事务
-    let t: i64 = clock.unix_timestamp; 
     let t_target = proof.last_hash_at.saturating_add(ONE_MINUTE); 
-    let t_spam = t_target.saturating_sub(TOLERANCE); 
+    let t_spam = t_target.saturating_sub(config.tolerance); 
     if t.lt(&t_spam) {
         return Err(LuckycoinError::Spam.into()); 
     }
+    let t_liveness = t_target.saturating_add(config.tolerance);
 
     // 验证哈希满足最低难度
     let hash = solution.to_hash(); 
@@ -80,73 +213,74 @@ pub fn process_mine(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
         return Err(LuckycoinError::HashTooEasy.into()); 
     }
 
-    // 规范化难度并计算奖励金额
-    let normalized_difficulty = difficulty.checked_sub(config.min_difficulty as u32).unwrap();
-    let mut reward = config
-        .base_reward_rate
-        .checked_mul(2u64.checked_pow(normalized_difficulty).unwrap())
-        .unwrap();
+    // 计算奖励金额
+    let Reward {
+        theoretical: reward,
+        actual: reward_actual,
+    } = calculate_reward(config, proof, bus, difficulty, t);
 
-    // 应用质押乘数
-    let mut bus_data = bus_info.data.borrow_mut();
-    let bus = Bus::try_from_bytes_mut(&mut bus_data)?;
-    if proof.balance.gt(&0) && proof.last_stake_at.saturating_add(ONE_MINUTE).lt(&t) {
-        // 计算质押奖励
-        if config.top_balance.gt(&0) {
-            let staking_reward = (reward as u128)
-                .checked_mul(proof.balance.min(config.top_balance) as u128)
-                .unwrap()
-                .checked_div(config.top_balance as u128)
-                .unwrap() as u64;
-            reward = reward.checked_add(staking_reward).unwrap();
-        }
-
-        // 更新总线质押跟踪器
-        if proof.balance.gt(&bus.top_balance) {
-            bus.top_balance = proof.balance; // 更新最高余额
-        }
+    // 更新总线质押跟踪器
+    if proof.stake.gt(&0)
+        && proof.last_stake_at.saturating_add(ONE_MINUTE).lt(&t)
+        && proof.stake.gt(&bus.top_balance)
+    {
+        bus.top_balance = proof.stake; // 更新最高余额
     }
 
-    // 应用活跃度惩罚
-    //
-    // The liveness penalty exists to ensure there is no "invisible" hashpower on the network. It
-    // should not be possible to spend ~1 hour on a given challenge and submit a hash with a large
-    // difficulty value to earn an outsized reward.
+    // 更新余额
     //
-    // The penalty works by halving the reward amount for every minute late the solution has been submitted.
-    // This ultimately drives the reward to zero given enough time (10-20 minutes).
-    let t_liveness = t_target.saturating_add(TOLERANCE);
-    if t.gt(&t_liveness) {
-        // 每分钟迟交奖励减半
-        let tardiness = t.saturating_sub(t_target) as u64;
-        let halvings = tardiness.saturating_div(ONE_MINUTE as u64);
-        if halvings.gt(&0) {
-            reward = reward.saturating_div(2u64.saturating_pow(halvings as u32));
-        }
+    // We track the theoretical rewards that would have been paid out ignoring the bus limit, so the
+    // base reward rate will be updated to account for the real hashpower on the network. The reward
+    // saturates for very hard hashes, so the tracker saturates as well.
+    bus.theoretical_rewards = bus.theoretical_rewards.saturating_add(reward);
+    bus.rewards = bus.rewards.checked_sub(reward_actual).ok_or(LuckycoinError::Overflow)?;
+    proof.earnings = proof.earnings.checked_add(reward_actual).ok_or(LuckycoinError::Overflow)?;
 
-        // 余秒线性衰减
-        let remainder_secs = tardiness.saturating_sub(halvings.saturating_mul(ONE_MINUTE as u64));
-        if remainder_secs.gt(&0) && reward.gt(&0) {
-            let penalty = reward
-                .saturating_div(2)
-                .saturating_mul(remainder_secs)
-                .saturating_div(ONE_MINUTE as u64);
-            reward = reward.saturating_sub(penalty);
-        }
+    // 支付幸运奖池
+    //
+    // A hash submitted on time wins the jackpot outright if it clears the jackpot difficulty. Every
+    // on-time hash also earns a ticket that is drawn on the next submission, using the hash of the slot
+    // in which the ticket was earned. That slot hash is only produced after the submitting transaction
+    // lands, so the miner cannot know it while searching for the hash. Late hashes earn nothing.
+    //
+    // The outcome of a ticket is known before the next submission, so a ticket is bound to the bus it
+    // was earned on and can only be drawn against that bus. Otherwise a winner could pick the bus with
+    // the largest jackpot.
+    let on_time = t.le(&t_liveness);
+    let mut jackpot = 0;
+    let strike = on_time
+        && config.jackpot_difficulty.gt(&0)
+        && (difficulty as u64).ge(&config.jackpot_difficulty);
+    let draw = proof.jackpot_slot.gt(&0)
+        && proof.jackpot_bus.eq(&bus.id)
+        && find_slot_hash(slot_hashes, proof.jackpot_slot)
+            .is_some_and(|h| is_jackpot_draw(&proof.jackpot_seed, &h, config.jackpot_odds));
+    if (strike || draw) && bus.jackpot.gt(&0) {
+        jackpot = bus.jackpot;
+        bus.jackpot = 0;
+        proof.earnings = proof.earnings.checked_add(jackpot).ok_or(LuckycoinError::Overflow)?;
     }
 
-    // 限制支付金额到总线剩余部分
-    let reward_actual = reward.min(bus.rewards).min(ONE_ORE); // 实际奖励
+    // 发放新的抽奖券。未开奖的抽奖券在开奖或插槽哈希过期后作废。
+    if on_time {
+        proof.jackpot_seed = hash.h;
+        proof.jackpot_slot = slot;
+        proof.jackpot_bus = bus.id;
+    } else {
+        proof.jackpot_seed = [0; 32];
+        proof.jackpot_slot = 0;
+        proof.jackpot_bus = 0;
+    }
 
-    // 更新余额
-    //
-    // We track the theoretical rewards that would have been paid out ignoring the bus limit, so the
-    // base reward rate will be updated to account for the real hashpower on the network.
-    bus.theoretical_rewards = bus.theoretical_rewards.checked_add(reward).unwrap();
-    bus.rewards = bus.rewards.checked_sub(reward_actual).unwrap();
-    proof.balance = proof.balance.checked_add(reward_actual).unwrap();
+    // 记录 bus 的负债，重置时结算到财库
+    bus.liabilities = bus
+        .liabilities
+        .checked_add(reward_actual)
+        .and_then(|liabilities| liabilities.checked_add(jackpot))
+        .ok_or(LuckycoinError::Overflow)?;
 
     // 将最近的插槽哈希哈希到下一个挑战中
+    let slot_hash = &slot_hashes[0..size_of::<SlotHash>()];
     // Hash a recent slot hash into the next challenge to prevent pre-mining attacks.
     //
     // The slot hashes are unpredictable values. By seeding the next challenge with the most recent slot hash,
@@ -154,7 +288,7 @@ pub fn process_mine(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
     proof.last_hash = hash.h;
     proof.challenge = hashv(&[
         hash.h.as_slice(),
-        &slot_hashes_sysvar.data.borrow()[0..size_of::<SlotHash>()],
+        slot_hash,
     ])
         .0;
 
@@ -163,36 +297,91 @@ pub fn process_mine(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
 
     // 更新生命周期统计
     proof.total_hashes = proof.total_hashes.saturating_add(1);
-    proof.total_rewards = proof.total_rewards.saturating_add(reward);
+    proof.total_rewards = proof.total_rewards.saturating_add(reward).saturating_add(jackpot);
 
-    // 记录挖矿奖励
-    //
-    // This data can be used by off-chain indexers to display mining stats.
-    set_return_data(
-        MineEvent {
-            difficulty: difficulty as u64,
-            reward: reward_actual,
-            timing: t.saturating_sub(t_liveness),
-        }.to_bytes(),
-    );
+    Ok(MineResult {
+        difficulty,
+        reward_actual,
+        jackpot,
+        timing: t.saturating_sub(t_liveness),
+    })
+}
 
-    Ok(())
+/// 在 SlotHashes 系统变量的数据中查找给定插槽的插槽哈希。
+///
+/// 数据以条目数量开头，随后是按插槽降序排列的 (插槽, 哈希) 条目。
+/// 插槽已经不在系统变量中时返回 `None`。
+pub fn find_slot_hash(slot_hashes: &[u8], slot: u64) -> Option<[u8; 32]> {
+    let len = u64::from_le_bytes(slot_hashes.get(0..8)?.try_into().ok()?) as usize;
+    let entries = slot_hashes.get(8..)?.chunks_exact(size_of::<SlotHash>()).take(len);
+    for entry in entries {
+        let entry_slot = u64::from_le_bytes(entry[0..8].try_into().ok()?);
+        if entry_slot.eq(&slot) {
+            return entry[8..40].try_into().ok();
+        }
+        if entry_slot.lt(&slot) {
+            break;
+        }
+    }
+    None
 }
 
-/// 认证证明账户，以防止 Sybil 攻击。
-fn authenticate(data: &[u8], proof_address: &Pubkey) -> ProgramResult {
-    if let Ok(Some(auth_address)) = parse_auth_address(data) {
-        if proof_address.ne(&auth_address) {
-            return Err(LuckycoinError::AuthFailed.into()); //返回错误，认证失败
+/// 使用抽奖券的种子和开奖插槽的插槽哈希开奖，中奖概率为 1/odds。
+pub fn is_jackpot_draw(seed: &[u8; 32], slot_hash: &[u8; 32], odds: u64) -> bool {
+    if odds.eq(&0) {
+        return false;
+    }
+    let roll = hashv(&[JACKPOT, seed.as_slice(), slot_hash.as_slice()]).0;
+    u64::from_le_bytes(roll[0..8].try_into().unwrap())
+        .checked_rem(odds)
+        .is_some_and(|r| r.eq(&0))
+}
+
+/// 按配置的认证模式认证证明账户，以防止 Sybil 攻击。
+///
+/// - 空操作模式：事务中第一条空操作程序指令必须携带 proof 地址。批量挖矿时该指令依次携带所有 proof 地址。
+/// - ed25519 模式：事务中必须包含一条 ed25519 预编译指令，由 `signers` 中的任意一个密钥对 proof 地址和
+///   当前挑战签名。调用者传入授权者和已经验证过挖矿权限的签名者，例如 miner set 中的矿工或会话密钥。
+///   批量挖矿时每个 proof 需要一个签名，可以位于同一条指令中。挑战在每次挖矿后都会改变，因此签名无法被重放。
+/// - 无认证模式：直接通过，仅用于测试网络。
+pub(crate) fn authenticate(
+    data: &[u8],
+    auth_mode: u64,
+    proof_address: &Pubkey,
+    signers: &[Pubkey],
+    challenge: &[u8; 32],
+) -> ProgramResult {
+    match auth_mode {
+        AUTH_MODE_NOOP => {
+            if let Ok(auth_addresses) = parse_auth_addresses(data) {
+                if !auth_addresses.contains(proof_address) {
+                    return Err(LuckycoinError::AuthFailed.into()); //返回错误，认证失败
+                }
+            } else {
+                return Err(LuckycoinError::AuthFailed.into()); // 发挥错误，认证失败
+            }
         }
-    } else {
-        return Err(LuckycoinError::AuthFailed.into()); // 发挥错误，认证失败
+        AUTH_MODE_ED25519 => {
+            if let Ok(auths) = parse_ed25519_auths(data) {
+                if !auths.iter().any(|(signer, message)| {
+                    signers.contains(signer)
+                        && message[..32].eq(proof_address.as_ref())
+                        && message[32..].eq(challenge.as_slice())
+                }) {
+                    return Err(LuckycoinError::AuthFailed.into());
+                }
+            } else {
+                return Err(LuckycoinError::AuthFailed.into());
+            }
+        }
+        AUTH_MODE_NONE => {}
+        _ => return Err(LuckycoinError::AuthFailed.into()),
     }
     Ok(())
 }
 
-/// 使用事务内省来解析认证的公钥
-fn parse_auth_address(data: &[u8]) -> Result<Option<Pubkey>, SanitizeError> {
+/// 使用事务内省来解析第一条空操作程序指令中携带的所有认证公钥
+fn parse_auth_addresses(data: &[u8]) -> Result<Vec<Pubkey>, SanitizeError> {
     // 当前字节索引初始化为0
     let mut curr = 0;
     let num_instructions = read_u16(&mut curr, data)?; // 读取指令数量
@@ -213,13 +402,442 @@ fn parse_auth_address(data: &[u8]) -> Result<Option<Pubkey>, SanitizeError> {
 
         // Introspect on the first noop instruction
         if program_id.eq(&NOOP_PROGRAM_ID) {
-            // Retrun address read from instruction data
-            curr += 2;
-            let address = read_pubkey(&mut curr, data)?;
-            return Ok(Some(address));
+            // Return addresses read from instruction data
+            let len = read_u16(&mut curr, data)? as usize;
+            let mut addresses = Vec::with_capacity(len / 32);
+            for _ in 0..len / 32 {
+                addresses.push(read_pubkey(&mut curr, data)?);
+            }
+            return Ok(addresses);
         }
     }
 
     // Default return none
-    Ok(None)
+    Ok(vec![])
+}
+
+/// ed25519 预编译指令数据中，签名数量、填充字节和一组签名偏移量的长度。
+const ED25519_DATA_START: usize = 2;
+
+/// ed25519 预编译指令中每个签名的偏移量结构的大小。
+const ED25519_OFFSETS_SIZE: usize = 14;
+
+/// 使用事务内省来解析所有 ed25519 预编译指令的签名者和消息。
+///
+/// 签名本身由运行时在执行事务前验证，这里只需要检查签名的内容。一条指令可以包含多个签名，
+/// 只有公钥和消息都位于该指令自身数据中的签名会被采用，引用其他指令数据的签名被忽略。
+fn parse_ed25519_auths(data: &[u8]) -> Result<Vec<(Pubkey, [u8; 64])>, SanitizeError> {
+    let mut auths = vec![];
+    let mut curr = 0;
+    let num_instructions = read_u16(&mut curr, data)?;
+    let pc = curr;
+
+    // 遍历事务指令
+    for i in 0..num_instructions as usize {
+        curr = pc + i * 2;
+        curr = read_u16(&mut curr, data)? as usize;
+
+        // 跳过账户
+        let num_accounts = read_u16(&mut curr, data)? as usize;
+        curr += num_accounts * 33;
+
+        // 读取指令的程序 id
+        let program_id = read_pubkey(&mut curr, data)?;
+        if program_id.ne(&ed25519_program::id()) {
+            continue;
+        }
+
+        // 读取指令数据
+        let len = read_u16(&mut curr, data)? as usize;
+        let ix_data = data.get(curr..curr + len).ok_or(SanitizeError::IndexOutOfBounds)?;
+        let Some(num_signatures) = ix_data.first() else {
+            continue;
+        };
+
+        // 解析每个签名的偏移量。u16::MAX 和当前指令的索引都表示数据位于该指令自身中。
+        let is_self = |index: u16| index.eq(&u16::MAX) || (index as usize).eq(&i);
+        for j in 0..*num_signatures as usize {
+            let mut offset = ED25519_DATA_START + j * ED25519_OFFSETS_SIZE;
+            let _signature_offset = read_u16(&mut offset, ix_data)?;
+            let _signature_ix = read_u16(&mut offset, ix_data)?;
+            let public_key_offset = read_u16(&mut offset, ix_data)? as usize;
+            let public_key_ix = read_u16(&mut offset, ix_data)?;
+            let message_offset = read_u16(&mut offset, ix_data)? as usize;
+            let message_size = read_u16(&mut offset, ix_data)? as usize;
+            let message_ix = read_u16(&mut offset, ix_data)?;
+            if !is_self(public_key_ix) || !is_self(message_ix) || message_size.ne(&64) {
+                continue;
+            }
+
+            // 读取签名者和消息
+            let mut key_curr = public_key_offset;
+            let signer = read_pubkey(&mut key_curr, ix_data)?;
+            let message: [u8; 64] = ix_data
+                .get(message_offset..message_offset + message_size)
+                .ok_or(SanitizeError::IndexOutOfBounds)?
+                .try_into()
+                .unwrap();
+            auths.push((signer, message));
+        }
+    }
+
+    Ok(auths)
+}
+
+/// 验证签名者可以为 proof 账户挖矿。
+///
+/// 签名者必须是 proof 的矿工、miner set 中未过期的授权矿工，或是有效会话的会话密钥。矿池 proof
+/// 的矿工是矿池账户本身，它无法在程序外签名，因此矿池的 proof 只能通过 PoolMine 挖矿。
+pub fn authorize_miner(
+    proof: &Proof,
+    signer: &Pubkey,
+    miner_set: Option<&MinerSet>,
+    session: Option<&Session>,
+    slot: u64,
+    t: i64,
+) -> Result<(), LuckycoinError> {
+    if proof.miner.eq(signer)
+        || miner_set.is_some_and(|miner_set| miner_set.is_authorized(signer, slot))
+        || session.is_some_and(|session| session.is_active(t))
+    {
+        return Ok(());
+    }
+    Err(LuckycoinError::MinerNotAuthorized)
+}
+
+#[cfg(test)]
+mod tests {
+    use drillx::Solution;
+    use luckycoin_api::{
+        consts::*,
+        error::LuckycoinError,
+        state::{Bus, Config, MinerSet, Proof, Session},
+    };
+    use solana_program::{ed25519_program, pubkey::Pubkey, sanitize::SanitizeError};
+    use steel::*;
+
+    use super::{
+        authenticate, authorize_miner, find_slot_hash, is_jackpot_draw, mine_solution,
+        parse_ed25519_auths,
+    };
+    use crate::open_pool::pool_proof;
+
+    /// 按指令系统变量的布局序列化事务指令，每条指令没有账户。
+    fn instructions_sysvar(instructions: &[(Pubkey, Vec<u8>)]) -> Vec<u8> {
+        let mut data = (instructions.len() as u16).to_le_bytes().to_vec();
+        let mut body = vec![];
+        let start = 2 + instructions.len() * 2;
+        for (program_id, ix_data) in instructions {
+            data.extend_from_slice(&((start + body.len()) as u16).to_le_bytes());
+            body.extend_from_slice(&0u16.to_le_bytes());
+            body.extend_from_slice(program_id.as_ref());
+            body.extend_from_slice(&(ix_data.len() as u16).to_le_bytes());
+            body.extend_from_slice(ix_data);
+        }
+        data.extend_from_slice(&body);
+        data
+    }
+
+    /// 构造 ed25519 预编译指令的数据。每个签名的数据引用 `index` 指向的指令。
+    fn ed25519_data(signatures: &[(Pubkey, [u8; 64])], index: u16) -> Vec<u8> {
+        let mut data = vec![signatures.len() as u8, 0];
+        let mut payload = vec![];
+        let start = 2 + signatures.len() * 14;
+        for (signer, message) in signatures {
+            let signature_offset = (start + payload.len()) as u16;
+            payload.extend_from_slice(&[0u8; 64]);
+            let public_key_offset = (start + payload.len()) as u16;
+            payload.extend_from_slice(signer.as_ref());
+            let message_offset = (start + payload.len()) as u16;
+            payload.extend_from_slice(message);
+            for value in [
+                signature_offset,
+                index,
+                public_key_offset,
+                index,
+                message_offset,
+                64,
+                index,
+            ] {
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        data.extend_from_slice(&payload);
+        data
+    }
+
+    fn message(byte: u8) -> [u8; 64] {
+        [byte; 64]
+    }
+
+    /// 为给定挑战求解一个有效的解决方案。
+    fn solve(challenge: &[u8; 32]) -> Solution {
+        (0u64..)
+            .find_map(|nonce| {
+                let nonce = nonce.to_le_bytes();
+                drillx::hash(challenge, &nonce)
+                    .ok()
+                    .map(|hash| Solution::new(hash.d, nonce))
+            })
+            .unwrap()
+    }
+
+    /// 任何有效哈希都能获得奖励、每张抽奖券都会中奖的配置，以及一个按时提交的 proof 账户。
+    fn mine_setup() -> (Config, Bus, Proof) {
+        let mut config = Config::zeroed();
+        config.base_reward_rate = 1;
+        config.epoch_duration = ONE_MINUTE * 10;
+        config.tolerance = TOLERANCE;
+        config.max_hash_reward = MAX_HASH_REWARD;
+        config.jackpot_odds = 1;
+        let mut bus = Bus::zeroed();
+        bus.rewards = BUS_EPOCH_REWARDS;
+        let proof = Proof::zeroed();
+        (config, bus, proof)
+    }
+
+    fn slot_hashes(entries: &[(u64, [u8; 32])]) -> Vec<u8> {
+        let mut data = (entries.len() as u64).to_le_bytes().to_vec();
+        for (slot, hash) in entries {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(hash);
+        }
+        data
+    }
+
+    #[test]
+    fn test_find_slot_hash() {
+        let data = slot_hashes(&[(12, [3; 32]), (10, [2; 32]), (9, [1; 32])]);
+        assert_eq!(find_slot_hash(&data, 12), Some([3; 32]));
+        assert_eq!(find_slot_hash(&data, 9), Some([1; 32]));
+
+        // 跳过的插槽和尚未产生的插槽没有插槽哈希
+        assert_eq!(find_slot_hash(&data, 11), None);
+        assert_eq!(find_slot_hash(&data, 13), None);
+
+        // 过期的插槽已经不在系统变量中
+        assert_eq!(find_slot_hash(&data, 8), None);
+    }
+
+    #[test]
+    fn test_find_slot_hash_malformed() {
+        assert_eq!(find_slot_hash(&[], 1), None);
+        let mut data = slot_hashes(&[(5, [1; 32])]);
+        data.truncate(30);
+        assert_eq!(find_slot_hash(&data, 5), None);
+    }
+
+    #[test]
+    fn test_is_jackpot_draw() {
+        let seed = [7; 32];
+        assert!(is_jackpot_draw(&seed, &[1; 32], 1));
+        assert!(!is_jackpot_draw(&seed, &[1; 32], 0));
+
+        // 同一张抽奖券的结果取决于开奖插槽的插槽哈希
+        let wins = (0..=255u8)
+            .filter(|i| is_jackpot_draw(&seed, &[*i; 32], 2))
+            .count();
+        assert!(wins > 64 && wins < 192);
+    }
+
+    #[test]
+    fn test_parse_ed25519_auths() {
+        let signer = Pubkey::new_unique();
+        let data = instructions_sysvar(&[
+            (Pubkey::new_unique(), vec![1, 2, 3]),
+            (ed25519_program::id(), ed25519_data(&[(signer, message(7))], u16::MAX)),
+        ]);
+        assert_eq!(parse_ed25519_auths(&data), Ok(vec![(signer, message(7))]));
+    }
+
+    #[test]
+    fn test_parse_ed25519_auths_multiple_signatures() {
+        // 一条指令中的多个签名和多条指令都会被解析
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let data = instructions_sysvar(&[
+            (
+                ed25519_program::id(),
+                ed25519_data(&[(a, message(1)), (b, message(2))], u16::MAX),
+            ),
+            (ed25519_program::id(), ed25519_data(&[(c, message(3))], u16::MAX)),
+        ]);
+        assert_eq!(
+            parse_ed25519_auths(&data),
+            Ok(vec![(a, message(1)), (b, message(2)), (c, message(3))])
+        );
+    }
+
+    #[test]
+    fn test_parse_ed25519_auths_cross_instruction() {
+        let signer = Pubkey::new_unique();
+
+        // 使用自身索引的签名数据位于指令自身中
+        let data = instructions_sysvar(&[
+            (Pubkey::new_unique(), vec![]),
+            (ed25519_program::id(), ed25519_data(&[(signer, message(1))], 1)),
+        ]);
+        assert_eq!(parse_ed25519_auths(&data), Ok(vec![(signer, message(1))]));
+
+        // 引用其他指令数据的签名被忽略
+        let data = instructions_sysvar(&[
+            (Pubkey::new_unique(), vec![]),
+            (ed25519_program::id(), ed25519_data(&[(signer, message(1))], 0)),
+        ]);
+        assert_eq!(parse_ed25519_auths(&data), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_parse_ed25519_auths_malformed() {
+        let signer = Pubkey::new_unique();
+        let valid = instructions_sysvar(&[(
+            ed25519_program::id(),
+            ed25519_data(&[(signer, message(1))], u16::MAX),
+        )]);
+
+        // 截断的系统变量数据
+        assert_eq!(parse_ed25519_auths(&[]), Err(SanitizeError::IndexOutOfBounds));
+        assert_eq!(
+            parse_ed25519_auths(&valid[..valid.len() - 1]),
+            Err(SanitizeError::IndexOutOfBounds)
+        );
+
+        // 签名数量超过实际的偏移量结构
+        let mut ix_data = ed25519_data(&[(signer, message(1))], u16::MAX);
+        ix_data[0] = 9;
+        ix_data.truncate(2 + 14 * 2);
+        let data = instructions_sysvar(&[(ed25519_program::id(), ix_data)]);
+        assert!(parse_ed25519_auths(&data).is_err());
+
+        // 公钥偏移量越界
+        let mut ix_data = ed25519_data(&[(signer, message(1))], u16::MAX);
+        ix_data[6..8].copy_from_slice(&u16::MAX.to_le_bytes());
+        let data = instructions_sysvar(&[(ed25519_program::id(), ix_data)]);
+        assert!(parse_ed25519_auths(&data).is_err());
+
+        // 消息长度不是 64 字节的签名被忽略
+        let mut ix_data = ed25519_data(&[(signer, message(1))], u16::MAX);
+        ix_data[12..14].copy_from_slice(&32u16.to_le_bytes());
+        let data = instructions_sysvar(&[(ed25519_program::id(), ix_data)]);
+        assert_eq!(parse_ed25519_auths(&data), Ok(vec![]));
+
+        // 没有签名的指令
+        let data = instructions_sysvar(&[(ed25519_program::id(), vec![])]);
+        assert_eq!(parse_ed25519_auths(&data), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_authenticate_ed25519_signers() {
+        let proof_address = Pubkey::new_unique();
+        let challenge = [9; 32];
+        let (authority, session_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut message = [0; 64];
+        message[..32].copy_from_slice(proof_address.as_ref());
+        message[32..].copy_from_slice(&challenge);
+        let signed_by = |key: Pubkey| {
+            let ix_data = ed25519_data(&[(key, message)], u16::MAX);
+            instructions_sysvar(&[(ed25519_program::id(), ix_data)])
+        };
+        let auth = |data: &[u8], signers: &[Pubkey]| {
+            authenticate(data, AUTH_MODE_ED25519, &proof_address, signers, &challenge)
+        };
+
+        // 授权者或已授权的会话密钥的签名都被接受
+        let signers = [authority, session_key];
+        assert_eq!(auth(&signed_by(authority), &signers), Ok(()));
+        assert_eq!(auth(&signed_by(session_key), &signers), Ok(()));
+
+        // 未被授权的密钥的签名被拒绝
+        let failed = Err(LuckycoinError::AuthFailed.into());
+        assert_eq!(auth(&signed_by(Pubkey::new_unique()), &signers), failed);
+        assert_eq!(auth(&signed_by(session_key), &[authority]), failed);
+
+        // 签名的挑战必须是当前的挑战
+        let data = signed_by(authority);
+        assert_eq!(
+            authenticate(&data, AUTH_MODE_ED25519, &proof_address, &signers, &[0; 32]),
+            failed
+        );
+    }
+
+    #[test]
+    fn test_authorize_miner() {
+        let mut proof = Proof::zeroed();
+        proof.miner = Pubkey::new_unique();
+        let signer = Pubkey::new_unique();
+        assert_eq!(authorize_miner(&proof, &proof.miner, None, None, 0, 0), Ok(()));
+        assert_eq!(
+            authorize_miner(&proof, &signer, None, None, 0, 0),
+            Err(LuckycoinError::MinerNotAuthorized)
+        );
+
+        // miner set 中未过期的矿工和有效会话都可以挖矿
+        let mut miner_set = MinerSet::zeroed();
+        miner_set.miners[0] = signer;
+        miner_set.expires_at[0] = 10;
+        assert_eq!(authorize_miner(&proof, &signer, Some(&miner_set), None, 9, 0), Ok(()));
+        assert_eq!(
+            authorize_miner(&proof, &signer, Some(&miner_set), None, 10, 0),
+            Err(LuckycoinError::MinerNotAuthorized)
+        );
+        let mut session = Session::zeroed();
+        session.expires_at = 100;
+        assert_eq!(authorize_miner(&proof, &signer, None, Some(&session), 0, 99), Ok(()));
+        assert_eq!(
+            authorize_miner(&proof, &signer, None, Some(&session), 0, 100),
+            Err(LuckycoinError::MinerNotAuthorized)
+        );
+    }
+
+    #[test]
+    fn test_authorize_miner_pool_proof() {
+        // 运营者不能用 Mine 直接为矿池 proof 挖矿
+        let pool = Pubkey::new_unique();
+        let operator = Pubkey::new_unique();
+        let proof = pool_proof(pool, [0; 32], 0);
+        assert_eq!(proof.authority, pool);
+        assert_eq!(
+            authorize_miner(&proof, &operator, None, None, 0, 0),
+            Err(LuckycoinError::MinerNotAuthorized)
+        );
+    }
+
+    #[test]
+    fn test_mine_solution_saturated_reward() {
+        // 奖励饱和到 u64::MAX 时挖矿不会溢出
+        let (mut config, mut bus, mut proof) = mine_setup();
+        config.base_reward_rate = u64::MAX;
+        bus.theoretical_rewards = 1;
+        let data = slot_hashes(&[(5, [1; 32])]);
+        let solution = solve(&proof.challenge);
+        let result = mine_solution(&config, &mut bus, &mut proof, solution, &data, 5, ONE_MINUTE);
+        assert_eq!(result.unwrap().reward_actual, MAX_HASH_REWARD);
+        assert_eq!(bus.theoretical_rewards, u64::MAX);
+        assert_eq!(proof.earnings, MAX_HASH_REWARD);
+    }
+
+    #[test]
+    fn test_mine_solution_jackpot_bound_to_bus() {
+        let (config, mut bus, mut proof) = mine_setup();
+        let data = slot_hashes(&[(5, [1; 32])]);
+        proof.jackpot_seed = [7; 32];
+        proof.jackpot_slot = 5;
+        proof.jackpot_bus = 1;
+        bus.jackpot = 1_000;
+
+        // 抽奖券不能在其他 bus 上开奖，提交后换成当前 bus 的新抽奖券
+        let solution = solve(&proof.challenge);
+        let result = mine_solution(&config, &mut bus, &mut proof, solution, &data, 6, ONE_MINUTE);
+        assert_eq!(result.unwrap().jackpot, 0);
+        assert_eq!(bus.jackpot, 1_000);
+        assert_eq!(proof.jackpot_bus, 0);
+        assert_eq!(proof.jackpot_slot, 6);
+
+        // 在获得抽奖券的 bus 上开奖
+        let data = slot_hashes(&[(6, [2; 32])]);
+        let solution = solve(&proof.challenge);
+        let t = proof.last_hash_at + ONE_MINUTE;
+        let result = mine_solution(&config, &mut bus, &mut proof, solution, &data, 7, t);
+        assert_eq!(result.unwrap().jackpot, 1_000);
+        assert_eq!(bus.jackpot, 0);
+    }
 }
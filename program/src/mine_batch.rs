@@ -0,0 +1,127 @@
+use std::mem::size_of;
+
+use drillx::Solution;
+use luckycoin_api::{
+    consts::*,
+    cpi::emit_event,
+    error::LuckycoinError,
+    event::{EventHeader, JackpotEvent, LuckycoinEvent, MineEvent},
+    instruction::Mine,
+    loaders::*,
+    state::{Bus, Config, Proof},
+};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    sysvar::{self, Sysvar},
+};
+use steel::*;
+
+use crate::mine::{authenticate, mine_solution};
+
+/// MineBatch 在一条指令中为多个 proof 账户提交解决方案，所有奖励都从同一个 bus 支付。
+///
+/// 指令数据中的第 i 个解决方案对应第 i 个 proof 账户。每个 proof 账户都按单独挖矿的规则独立验证、
+/// 认证和更新，签名者必须是所有 proof 账户的矿工。任何一个解决方案无效时整条指令失败。
+pub fn process_mine_batch(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [
+        signer,
+        bus_info,
+        config_info,
+        instructions_sysvar,
+        slot_hashes_sysvar,
+        event_authority_info,
+        luckycoin_program,
+        proof_infos @ ..
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any_bus(bus_info, true)?;
+    load_config(config_info, false)?;
+    load_sysvar(instructions_sysvar, sysvar::instructions::id())?;
+    load_sysvar(slot_hashes_sysvar, sysvar::slot_hashes::id())?;
+    load_event_authority(event_authority_info)?;
+    load_program(luckycoin_program, luckycoin_api::id())?;
+
+    // Parse args. 每个 proof 账户对应一个解决方案。
+    let solutions = data.chunks_exact(size_of::<Mine>());
+    if !solutions.remainder().is_empty()
+        || solutions.len().ne(&proof_infos.len())
+        || proof_infos.is_empty()
+        || proof_infos.len().gt(&MAX_MINE_BATCH)
+    {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // 检查挖矿是否已被暂停
+    let config_data = config_info.data.borrow();
+    let config = Config::try_from_bytes(&config_data)?;
+    if config.is_paused(PAUSE_MINE) {
+        return Err(LuckycoinError::Paused.into());
+    }
+
+    // 依次处理每个解决方案
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+    let slot_hashes = slot_hashes_sysvar.data.borrow();
+    for (proof_info, solution) in proof_infos.iter().zip(solutions) {
+        let args = Mine::try_from_bytes(solution)?;
+        load_any_proof(proof_info, true)?;
+        let mut bus_data = bus_info.data.borrow_mut();
+        let bus = Bus::try_from_bytes_mut(&mut bus_data)?;
+        let mut proof_data = proof_info.data.borrow_mut();
+        let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
+
+        // 验证签名者是 proof 的矿工
+        if proof.miner.ne(signer.key) {
+            return Err(LuckycoinError::MinerNotAuthorized.into());
+        }
+
+        // 认证证明账户
+        authenticate(
+            &instructions_sysvar.data.borrow(),
+            config.auth_mode,
+            proof_info.key,
+            &[proof.authority, *signer.key],
+            &proof.challenge,
+        )?;
+
+        let result = mine_solution(
+            config,
+            bus,
+            proof,
+            Solution::new(args.digest, args.nonce),
+            &slot_hashes,
+            clock.slot,
+            clock.unix_timestamp,
+        )?;
+
+        // 记录幸运奖池的支付
+        if result.jackpot.gt(&0) {
+            let event = JackpotEvent {
+                header: EventHeader::new(LuckycoinEvent::Jackpot),
+                authority: proof.authority,
+                bus: bus.id,
+                difficulty: result.difficulty as u64,
+                amount: result.jackpot,
+                timing: clock.unix_timestamp,
+            };
+            emit_event(event_authority_info, luckycoin_program, event.to_bytes())?;
+        }
+
+        // 记录挖矿奖励
+        let event = MineEvent {
+            header: EventHeader::new(LuckycoinEvent::Mine),
+            difficulty: result.difficulty as u64,
+            reward: result.reward_actual,
+            timing: result.timing,
+        };
+        emit_event(event_authority_info, luckycoin_program, event.to_bytes())?;
+    }
+
+    Ok(())
+}
@@ -2,6 +2,7 @@ use std::mem::size_of;
 
 use luckycoin_api::{
     consts::*,
+    event::{EventHeader, LuckycoinEvent, OpenEvent},
     instruction::Open,
     state::Proof,
     loaders::*,
@@ -12,12 +13,13 @@ use solana_program::{
     entrypoint::ProgramResult,
     keccak::hashv,
     program_error::ProgramError,
+    pubkey::Pubkey,
     slot_hashes::SlotHash,
     system_program,
     sysvar::{self, Sysvar},
 };
 use steel::*;
-use luckycoin_api::cpi::create_pda;
+use luckycoin_api::cpi::{create_pda, emit_event};
 
 pub fn process_open(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse args.
@@ -30,7 +32,9 @@ pub fn process_open(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
         payer_info, 
         proof_info, 
         system_program, 
-        slot_hashes_info
+        slot_hashes_info,
+        event_authority_info,
+        luckycoin_program
     ] = accounts 
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -47,6 +51,8 @@ pub fn process_open(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
     )?;
     load_program(system_program, system_program::id())?;
     load_sysvar(slot_hashes_info, sysvar::slot_hashes::id())?;
+    load_event_authority(event_authority_info)?;
+    load_program(luckycoin_program, luckycoin_api::id())?;
 
     // Initialize proof.
     create_pda(
@@ -66,7 +72,7 @@ pub fn process_open(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
     let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
     // 将矿工的签名者公钥设置为该证明的权威账户（authority），标识该矿工的所有权。
     proof.authority = *signer.key;
-    proof.balance = 0;
+    proof.earnings = 0;
     // 计算并设置挑战值(计算一个挑战值，它是通过哈希签名者的公钥和当前插槽哈希信息得到的)。这将用于后续验证挖矿
     proof.challenge = hashv(&[
         signer.key.as_ref(),
@@ -82,6 +88,22 @@ pub fn process_open(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
     // 初始化总哈希和总奖励
     proof.total_hashes = 0;
     proof.total_rewards = 0;
+    // 初始化质押本金和锁定状态
+    proof.stake = 0;
+    proof.lock_tier = 0;
+    proof.lock_until = 0;
+    // 初始化待处理的授权者转移
+    proof.pending_authority = Pubkey::default();
+
+    // 记录创建事件
+    let event = OpenEvent {
+        header: EventHeader::new(LuckycoinEvent::Open),
+        authority: proof.authority,
+        miner: proof.miner,
+        proof: *proof_info.key,
+        timing: clock.unix_timestamp,
+    };
+    emit_event(event_authority_info, luckycoin_program, event.to_bytes())?;
 
     Ok(())
 }
@@ -0,0 +1,114 @@
+use std::mem::size_of;
+
+use luckycoin_api::{
+    consts::*,
+    cpi::create_pda,
+    instruction::OpenPool,
+    loaders::*,
+    state::{Pool, Proof},
+};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    keccak::hashv,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    slot_hashes::SlotHash,
+    system_program,
+    sysvar::{self, Sysvar},
+};
+use steel::*;
+
+/// OpenPool 为运营者创建一个矿池，以及由矿池拥有的 proof 账户。
+pub fn process_open_pool(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = OpenPool::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [
+        signer, 
+        pool_info, 
+        proof_info, 
+        system_program, 
+        slot_hashes_info
+    ] = accounts 
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_uninitialized_pda(
+        pool_info,
+        &[POOL, signer.key.as_ref()],
+        args.pool_bump,
+        &luckycoin_api::id(),
+    )?;
+    load_uninitialized_pda(
+        proof_info,
+        &[PROOF, pool_info.key.as_ref()],
+        args.proof_bump,
+        &luckycoin_api::id(),
+    )?;
+    load_program(system_program, system_program::id())?;
+    load_sysvar(slot_hashes_info, sysvar::slot_hashes::id())?;
+
+    // Initialize pool.
+    create_pda(
+        pool_info,
+        &luckycoin_api::id(),
+        8 + size_of::<Pool>(),
+        &[POOL, signer.key.as_ref(), &[args.pool_bump]],
+        system_program,
+        signer,
+    )?;
+    let mut pool_data = pool_info.data.borrow_mut();
+    pool_data[0] = Pool::discriminator() as u8;
+    let pool = Pool::try_from_bytes_mut(&mut pool_data)?;
+    pool.operator = *signer.key;
+    pool.proof = *proof_info.key;
+    pool.round = 0;
+    pool.best_member = Pubkey::default();
+    pool.best_digest = [0; 16];
+    pool.best_nonce = [0; 8];
+    pool.best_difficulty = 0;
+    pool.total_work = 0;
+    pool.total_members = 0;
+    pool.total_rewards = 0;
+    pool.unsettled_rewards = 0;
+    pool.unsettled_work = 0;
+
+    // Initialize proof. 矿池既是 proof 账户的授权者也是矿工。
+    create_pda(
+        proof_info,
+        &luckycoin_api::id(),
+        8 + size_of::<Proof>(),
+        &[PROOF, pool_info.key.as_ref(), &[args.proof_bump]],
+        system_program,
+        signer,
+    )?;
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+    let mut proof_data = proof_info.data.borrow_mut();
+    proof_data[0] = Proof::discriminator() as u8;
+    let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
+    let challenge = hashv(&[
+        pool_info.key.as_ref(),
+        &slot_hashes_info.data.borrow()[0..size_of::<SlotHash>()],
+    ]).0;
+    *proof = pool_proof(*pool_info.key, challenge, clock.unix_timestamp);
+
+    Ok(())
+}
+
+/// 矿池 proof 账户的初始状态。
+///
+/// 矿池既是授权者也是矿工。矿池账户无法在程序外签名，因此运营者不能用 Mine 直接为矿池 proof 挖矿，
+/// 只能通过 PoolMine 提交成员的解决方案，确保收益经过成员结算。
+pub fn pool_proof(pool: Pubkey, challenge: [u8; 32], t: i64) -> Proof {
+    let mut proof = Proof::zeroed();
+    proof.authority = pool;
+    proof.challenge = challenge;
+    proof.last_hash_at = t;
+    proof.last_stake_at = t;
+    proof.miner = pool;
+    proof
+}
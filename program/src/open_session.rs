@@ -0,0 +1,80 @@
+use std::mem::size_of;
+
+use luckycoin_api::{
+    consts::*,
+    cpi::create_pda,
+    error::LuckycoinError,
+    instruction::OpenSession,
+    loaders::*,
+    state::{Proof, Session},
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program_error::ProgramError, system_program, sysvar::Sysvar,
+};
+use steel::*;
+
+/// OpenSession 由 proof 授权者将挖矿权限委托给一个会话密钥，直到给定的时间戳为止。
+///
+/// 如果领取上限大于 0，会话密钥还可以向固定的受益人领取不超过上限的奖励。
+/// 这样无人值守的矿机无需持有授权者的密钥即可运行。session 账户计入 proof 账户的关联账户数量，
+/// 在 proof 账户关闭、回收或转移时一并关闭。
+pub fn process_open_session(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // 解析参数
+    let args = OpenSession::try_from_bytes(data)?;
+    let expires_at = i64::from_le_bytes(args.expires_at);
+    let claim_limit = u64::from_le_bytes(args.claim_limit);
+
+    // 加载账户
+    let [signer, key_info, beneficiary_info, session_info, proof_info, system_program] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any(key_info, false)?;
+    if claim_limit.gt(&0) {
+        load_token_account(beneficiary_info, None, &MINT_ADDRESS, false)?;
+    } else {
+        load_any(beneficiary_info, false)?;
+    }
+    load_proof(proof_info, signer.key, true)?;
+    load_uninitialized_pda(
+        session_info,
+        &[SESSION, proof_info.key.as_ref(), key_info.key.as_ref()],
+        args.bump,
+        &luckycoin_api::id(),
+    )?;
+    load_program(system_program, system_program::id())?;
+
+    // 验证会话尚未过期
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+    if expires_at.le(&clock.unix_timestamp) {
+        return Err(LuckycoinError::SessionInvalid.into());
+    }
+
+    // 初始化 session 账户
+    create_pda(
+        session_info,
+        &luckycoin_api::id(),
+        8 + size_of::<Session>(),
+        &[SESSION, proof_info.key.as_ref(), key_info.key.as_ref(), &[args.bump]],
+        system_program,
+        signer,
+    )?;
+    let mut session_data = session_info.data.borrow_mut();
+    session_data[0] = Session::discriminator() as u8;
+    let session = Session::try_from_bytes_mut(&mut session_data)?;
+    session.proof = *proof_info.key;
+    session.key = *key_info.key;
+    session.beneficiary = *beneficiary_info.key;
+    session.expires_at = expires_at;
+    session.claim_limit = claim_limit;
+    session.claimed = 0;
+
+    // 记录关联账户，关闭 proof 账户之前必须先关闭 session
+    let mut proof_data = proof_info.data.borrow_mut();
+    let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
+    proof.linked_accounts = proof.linked_accounts.checked_add(1).unwrap();
+
+    Ok(())
+}
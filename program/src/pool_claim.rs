@@ -0,0 +1,183 @@
+use luckycoin_api::{
+    consts::*,
+    cpi::{emit_event, transfer_checked_signed},
+    error::LuckycoinError,
+    event::{ClaimEvent, EventHeader, LuckycoinEvent},
+    instruction::PoolClaim,
+    loaders::*,
+    state::{Config, Member, Pool, Proof, Treasury},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+use steel::*;
+
+use crate::{claim::calculate_claim_fee, pool_settle::settle_member};
+
+/// PoolClaim 允许成员从矿池 proof 账户的收益中领取自己的份额。
+///
+/// 与单独挖矿的领取一样按配置的比例收取手续费，并记录相同的领取事件。手续费金库代币账户是可选的
+/// 尾部账户，手续费为 0 时不需要提供。
+pub fn process_pool_claim(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    let args = PoolClaim::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    let [
+        signer, 
+        beneficiary_info, 
+        config_info, 
+        pool_info, 
+        member_info, 
+        proof_info, 
+        treasury_info, 
+        treasury_tokens_info, 
+        token_program,
+        mint_info,
+        event_authority_info,
+        luckycoin_program,
+        optional_infos @ ..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    // 加载受益人的代币账户，确保其与预期的铸币地址匹配。
+    load_token_account(
+        beneficiary_info, 
+        None, 
+        &MINT_ADDRESS, 
+        true
+    )?;
+    load_config(config_info, false)?;
+    load_any_pool(pool_info, true)?;
+    load_member(member_info, signer.key, pool_info.key, true)?;
+    load_proof(proof_info, pool_info.key, true)?;
+    load_treasury(treasury_info, true)?;
+    load_treasury_tokens(treasury_tokens_info, true)?;
+    load_mint(mint_info, MINT_ADDRESS, false)?;
+    load_token_program(token_program)?;
+    load_event_authority(event_authority_info)?;
+    load_program(luckycoin_program, luckycoin_api::id())?;
+
+    // 检查领取是否已被暂停
+    let config_data = config_info.data.borrow();
+    let config = Config::try_from_bytes(&config_data)?;
+    if config.is_paused(PAUSE_CLAIM) {
+        return Err(LuckycoinError::Paused.into());
+    }
+
+    // 计算领取手续费。手续费为 0 时不使用手续费金库，也不检查该账户。
+    let (fee, net) = calculate_claim_fee(amount, config.claim_fee_bps)?;
+    let fee_vault_tokens_info = load_optional_fee_vault_tokens(optional_infos, fee)?;
+
+    // 先结算成员上一轮的工作量，再更新成员的余额
+    let mut pool_data = pool_info.data.borrow_mut();
+    let pool = Pool::try_from_bytes_mut(&mut pool_data)?;
+    let mut member_data = member_info.data.borrow_mut();
+    let member = Member::try_from_bytes_mut(&mut member_data)?;
+    claim_member_balance(pool, member, amount)?;
+
+    // 更新矿池的收益。矿池的收益等于所有成员余额加上尚未结算的奖励，因此不会不足。
+    let mut proof_data = proof_info.data.borrow_mut();
+    let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
+    proof.earnings = proof
+        .earnings
+        .checked_sub(amount)
+        .ok_or(LuckycoinError::ClaimTooLarge)?;
+
+    // 记录财库的支出
+    let mut treasury_data = treasury_info.data.borrow_mut();
+    let treasury = Treasury::try_from_bytes_mut(&mut treasury_data)?;
+    treasury.total_debited = treasury
+        .total_debited
+        .checked_add(amount)
+        .ok_or(LuckycoinError::Overflow)?;
+    drop(treasury_data);
+
+    // 从国库向受益人账户转移扣除手续费后的代币。
+    transfer_checked_signed(
+        treasury_info,
+        treasury_tokens_info,
+        mint_info,
+        beneficiary_info,
+        token_program,
+        net,
+        &[&[TREASURY, &[TREASURY_BUMP]]],
+    )?;
+
+    // 将手续费转入手续费金库
+    if let Some(fee_vault_tokens_info) = fee_vault_tokens_info {
+        transfer_checked_signed(
+            treasury_info,
+            treasury_tokens_info,
+            mint_info,
+            fee_vault_tokens_info,
+            token_program,
+            fee,
+            &[&[TREASURY, &[TREASURY_BUMP]]],
+        )?;
+    }
+
+    // 记录领取事件
+    let event = ClaimEvent {
+        header: EventHeader::new(LuckycoinEvent::Claim),
+        authority: proof.authority,
+        beneficiary: *beneficiary_info.key,
+        amount,
+        earnings: proof.earnings,
+        fee,
+        net,
+    };
+    emit_event(event_authority_info, luckycoin_program, event.to_bytes())?;
+
+    Ok(())
+}
+
+/// 结算成员上一轮的工作量，并从成员的余额中扣除领取的金额。
+pub fn claim_member_balance(
+    pool: &mut Pool,
+    member: &mut Member,
+    amount: u64,
+) -> Result<(), LuckycoinError> {
+    settle_member(pool, member)?;
+    member.balance = member
+        .balance
+        .checked_sub(amount)
+        .ok_or(LuckycoinError::ClaimTooLarge)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use luckycoin_api::{
+        error::LuckycoinError,
+        state::{Member, Pool},
+    };
+    use steel::*;
+
+    use super::claim_member_balance;
+
+    #[test]
+    fn test_claim_member_balance_settles_first() {
+        let mut pool = Pool::zeroed();
+        pool.round = 1;
+        pool.unsettled_rewards = 500;
+        pool.unsettled_work = 2;
+        let mut member = Member::zeroed();
+        member.balance = 100;
+        member.work = 1;
+        assert_eq!(claim_member_balance(&mut pool, &mut member, 350), Ok(()));
+        assert_eq!(member.balance, 0);
+        assert_eq!(pool.unsettled_rewards, 250);
+    }
+
+    #[test]
+    fn test_claim_member_balance_too_large() {
+        let mut pool = Pool::zeroed();
+        let mut member = Member::zeroed();
+        member.balance = 100;
+        assert_eq!(
+            claim_member_balance(&mut pool, &mut member, 101),
+            Err(LuckycoinError::ClaimTooLarge)
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use drillx::Solution;
+use luckycoin_api::{
+    consts::*,
+    cpi::emit_event,
+    error::LuckycoinError,
+    event::{EventHeader, JackpotEvent, LuckycoinEvent, MineEvent},
+    instruction::PoolMine,
+    loaders::*,
+    state::{Bus, Config, Pool, Proof},
+};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{self, Sysvar},
+};
+use steel::*;
+
+use crate::mine::{authenticate, mine_solution};
+
+/// PoolMine 提交矿池本轮最好的解决方案，并记录本轮的奖励和工作量。
+///
+/// 成员的份额不在此处分配，而是之后由 PoolSubmit、PoolClaim 或 PoolSettle 逐个结算，
+/// 因此指令的大小与成员数量无关。上一轮的成员必须全部结算完，才能提交下一轮。
+pub fn process_pool_mine(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    PoolMine::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [
+        signer, 
+        bus_info, 
+        config_info, 
+        pool_info, 
+        proof_info, 
+        instructions_sysvar, 
+        slot_hashes_sysvar, 
+        event_authority_info, 
+        luckycoin_program
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any_bus(bus_info, true)?;
+    load_config(config_info, false)?;
+    load_pool(pool_info, signer.key, true)?;
+    load_proof(proof_info, pool_info.key, true)?;
+    load_sysvar(instructions_sysvar, sysvar::instructions::id())?;
+    load_sysvar(slot_hashes_sysvar, sysvar::slot_hashes::id())?;
+    load_event_authority(event_authority_info)?;
+    load_program(luckycoin_program, luckycoin_api::id())?;
+
+    // 检查挖矿是否已被暂停
+    let config_data = config_info.data.borrow();
+    let config = Config::try_from_bytes(&config_data)?;
+    if config.is_paused(PAUSE_MINE) {
+        return Err(LuckycoinError::Paused.into());
+    }
+
+    // 验证本轮有可提交的解决方案
+    let mut pool_data = pool_info.data.borrow_mut();
+    let pool = Pool::try_from_bytes_mut(&mut pool_data)?;
+    if pool.total_work.eq(&0) {
+        return Err(LuckycoinError::PoolEmpty.into());
+    }
+
+    // 提交最好的解决方案
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+    let mut bus_data = bus_info.data.borrow_mut();
+    let bus = Bus::try_from_bytes_mut(&mut bus_data)?;
+    let mut proof_data = proof_info.data.borrow_mut();
+    let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
+
+    // 旧版本的矿池 proof 以运营者为矿工，首次提交时将矿工改为矿池，之后运营者不能再用 Mine 挖矿
+    proof.miner = *pool_info.key;
+
+    // 认证证明账户。矿池的 proof 授权者是 PDA，因此由运营者签名。
+    authenticate(
+        &instructions_sysvar.data.borrow(),
+        config.auth_mode,
+        proof_info.key,
+        &[*signer.key],
+        &proof.challenge,
+    )?;
+
+    let result = mine_solution(
+        config,
+        bus,
+        proof,
+        Solution::new(pool.best_digest, pool.best_nonce),
+        &slot_hashes_sysvar.data.borrow(),
+        clock.slot,
+        clock.unix_timestamp,
+    )?;
+
+    // 记录本轮的奖励，包括矿池赢得的幸运奖池，并进入下一轮
+    let total_reward = result
+        .reward_actual
+        .checked_add(result.jackpot)
+        .ok_or(LuckycoinError::Overflow)?;
+    finish_round(pool, total_reward)?;
+
+    // 记录幸运奖池的支付
+    if result.jackpot.gt(&0) {
+        let event = JackpotEvent {
+            header: EventHeader::new(LuckycoinEvent::Jackpot),
+            authority: proof.authority,
+            bus: bus.id,
+            difficulty: result.difficulty as u64,
+            amount: result.jackpot,
+            timing: clock.unix_timestamp,
+        };
+        emit_event(event_authority_info, luckycoin_program, event.to_bytes())?;
+    }
+
+    // 记录挖矿奖励
+    let event = MineEvent {
+        header: EventHeader::new(LuckycoinEvent::Mine),
+        difficulty: result.difficulty as u64,
+        reward: result.reward_actual,
+        timing: result.timing,
+    };
+    emit_event(event_authority_info, luckycoin_program, event.to_bytes())?;
+
+    Ok(())
+}
+
+/// 结束矿池的当前轮次。本轮的奖励和工作量留待成员结算，矿池进入下一轮。
+pub fn finish_round(pool: &mut Pool, total_reward: u64) -> Result<(), LuckycoinError> {
+    // 上一轮必须已经结算完
+    if pool.unsettled_work.gt(&0) {
+        return Err(LuckycoinError::PoolUnsettled);
+    }
+
+    // 记录本轮待结算的奖励和工作量
+    pool.unsettled_rewards = pool
+        .unsettled_rewards
+        .checked_add(total_reward)
+        .ok_or(LuckycoinError::Overflow)?;
+    pool.unsettled_work = pool.total_work;
+    pool.total_rewards = pool.total_rewards.saturating_add(total_reward);
+
+    // 进入下一轮
+    pool.round = pool.round.saturating_add(1);
+    pool.best_member = Pubkey::default();
+    pool.best_digest = [0; 16];
+    pool.best_nonce = [0; 8];
+    pool.best_difficulty = 0;
+    pool.total_work = 0;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use luckycoin_api::{
+        error::LuckycoinError,
+        state::{Member, Pool},
+    };
+    use steel::*;
+
+    use super::finish_round;
+    use crate::pool_settle::settle_member;
+
+    #[test]
+    fn test_finish_round() {
+        let mut pool = Pool::zeroed();
+        pool.total_work = 3;
+        pool.best_difficulty = 12;
+        assert_eq!(finish_round(&mut pool, 900), Ok(()));
+        assert_eq!(pool.round, 1);
+        assert_eq!(pool.unsettled_rewards, 900);
+        assert_eq!(pool.unsettled_work, 3);
+        assert_eq!(pool.total_rewards, 900);
+        assert_eq!(pool.total_work, 0);
+        assert_eq!(pool.best_difficulty, 0);
+    }
+
+    #[test]
+    fn test_finish_round_unsettled() {
+        let mut pool = Pool::zeroed();
+        pool.total_work = 3;
+        finish_round(&mut pool, 900).unwrap();
+        pool.total_work = 1;
+        assert_eq!(finish_round(&mut pool, 900), Err(LuckycoinError::PoolUnsettled));
+
+        // 结算上一轮的成员之后才能进入下一轮
+        let mut member = Member::zeroed();
+        member.work = 3;
+        assert_eq!(settle_member(&mut pool, &mut member), Ok(900));
+        assert_eq!(finish_round(&mut pool, 100), Ok(()));
+        assert_eq!(pool.round, 2);
+        assert_eq!(pool.unsettled_rewards, 100);
+    }
+}
@@ -0,0 +1,133 @@
+use luckycoin_api::{
+    error::LuckycoinError,
+    instruction::PoolSettle,
+    loaders::*,
+    state::{Member, Pool},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+use steel::*;
+
+/// PoolSettle 将上一轮的奖励结算给给定的成员，任何人都可以调用。
+///
+/// 成员提交解决方案或领取奖励时会自动结算，因此该指令只用于结算不再活跃的成员。
+/// 运营者在提交下一轮之前，必须结算完上一轮所有贡献了工作量的成员，可以分多笔交易完成。
+pub fn process_pool_settle(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    PoolSettle::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer, pool_info, member_infos @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any_pool(pool_info, true)?;
+
+    // 依次结算每个成员
+    let mut pool_data = pool_info.data.borrow_mut();
+    let pool = Pool::try_from_bytes_mut(&mut pool_data)?;
+    for member_info in member_infos {
+        load_any_member(member_info, true)?;
+        let mut member_data = member_info.data.borrow_mut();
+        let member = Member::try_from_bytes_mut(&mut member_data)?;
+        if member.pool.ne(pool_info.key) {
+            return Err(LuckycoinError::PoolMembersInvalid.into());
+        }
+        settle_member(pool, member)?;
+    }
+
+    Ok(())
+}
+
+/// 将成员在已结束轮次中的工作量结算为可领取的余额，并返回结算的份额。
+///
+/// 份额按成员工作量占上一轮未结算工作量的比例计算，最后一个结算的成员获得取整产生的余数，
+/// 因此上一轮的奖励总是被完整分配。成员没有已结束轮次的工作量时不做任何修改。
+pub fn settle_member(pool: &mut Pool, member: &mut Member) -> Result<u64, LuckycoinError> {
+    // 当前轮次的工作量尚未获得奖励
+    if member.round.eq(&pool.round) {
+        return Ok(0);
+    }
+
+    // 结算上一轮的工作量。矿池在上一轮结算完之前不会进入下一轮，
+    // 因此成员未结算的工作量总是属于上一轮。
+    let work = member.work;
+    let share = if work.eq(&0) {
+        0
+    } else if work.ge(&pool.unsettled_work) {
+        pool.unsettled_rewards
+    } else {
+        (pool.unsettled_rewards as u128)
+            .checked_mul(work as u128)
+            .and_then(|x| x.checked_div(pool.unsettled_work as u128))
+            .ok_or(LuckycoinError::Overflow)? as u64
+    };
+    pool.unsettled_rewards = pool.unsettled_rewards.saturating_sub(share);
+    pool.unsettled_work = pool.unsettled_work.saturating_sub(work);
+    member.balance = member.balance.checked_add(share).ok_or(LuckycoinError::Overflow)?;
+    member.total_work = member.total_work.saturating_add(work);
+    member.total_rewards = member.total_rewards.saturating_add(share);
+    member.round = pool.round;
+    member.work = 0;
+
+    Ok(share)
+}
+
+#[cfg(test)]
+mod tests {
+    use luckycoin_api::state::{Member, Pool};
+    use steel::*;
+
+    use super::settle_member;
+
+    #[test]
+    fn test_settle_member_pro_rata() {
+        let mut pool = Pool::zeroed();
+        pool.round = 1;
+        pool.unsettled_rewards = 1_000;
+        pool.unsettled_work = 3;
+        let mut a = Member::zeroed();
+        a.work = 1;
+        let mut b = Member::zeroed();
+        b.work = 2;
+
+        // 最后一个结算的成员获得取整余数
+        assert_eq!(settle_member(&mut pool, &mut a), Ok(333));
+        assert_eq!(settle_member(&mut pool, &mut b), Ok(667));
+        assert_eq!(pool.unsettled_rewards, 0);
+        assert_eq!(pool.unsettled_work, 0);
+        assert_eq!(a.balance, 333);
+        assert_eq!(b.balance, 667);
+        assert_eq!(b.total_work, 2);
+        assert_eq!((a.round, a.work), (1, 0));
+    }
+
+    #[test]
+    fn test_settle_member_is_idempotent() {
+        let mut pool = Pool::zeroed();
+        pool.round = 1;
+        pool.unsettled_rewards = 1_000;
+        pool.unsettled_work = 4;
+        let mut member = Member::zeroed();
+        member.work = 1;
+        assert_eq!(settle_member(&mut pool, &mut member), Ok(250));
+        assert_eq!(settle_member(&mut pool, &mut member), Ok(0));
+        assert_eq!(member.balance, 250);
+        assert_eq!(pool.unsettled_work, 3);
+    }
+
+    #[test]
+    fn test_settle_member_current_round() {
+        let mut pool = Pool::zeroed();
+        pool.round = 1;
+        pool.unsettled_rewards = 1_000;
+        pool.unsettled_work = 4;
+        let mut member = Member::zeroed();
+        member.round = 1;
+        member.work = 4;
+        assert_eq!(settle_member(&mut pool, &mut member), Ok(0));
+        assert_eq!(member.work, 4);
+        assert_eq!(pool.unsettled_rewards, 1_000);
+    }
+}
@@ -0,0 +1,123 @@
+use drillx::Solution;
+use luckycoin_api::{
+    consts::*,
+    error::LuckycoinError,
+    instruction::PoolSubmit,
+    loaders::*,
+    state::{Config, Member, Pool, Proof},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+use steel::*;
+
+use crate::pool_settle::settle_member;
+
+/// PoolSubmit 记录成员针对矿池当前挑战提交的解决方案。
+///
+/// 每个成员每轮只记录其最好的解决方案，贡献的工作量按 `2^(difficulty - min_difficulty)` 计算，
+/// 与挖矿奖励的增长方式一致。矿池同时记录本轮中最好的解决方案，供运营者提交。
+/// 随机数的高 32 位必须等于成员编号，因此成员之间不能冒领彼此的解决方案。
+pub fn process_pool_submit(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = PoolSubmit::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer, config_info, pool_info, member_info, proof_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_config(config_info, false)?;
+    load_any_pool(pool_info, true)?;
+    load_member(member_info, signer.key, pool_info.key, true)?;
+    load_proof(proof_info, pool_info.key, false)?;
+
+    // 检查挖矿是否已被暂停
+    let config_data = config_info.data.borrow();
+    let config = Config::try_from_bytes(&config_data)?;
+    if config.is_paused(PAUSE_MINE) {
+        return Err(LuckycoinError::Paused.into());
+    }
+
+    // 验证解决方案符合矿池的当前挑战，并且随机数属于该成员
+    let proof_data = proof_info.data.borrow();
+    let proof = Proof::try_from_bytes(&proof_data)?;
+    let mut pool_data = pool_info.data.borrow_mut();
+    let pool = Pool::try_from_bytes_mut(&mut pool_data)?;
+    let mut member_data = member_info.data.borrow_mut();
+    let member = Member::try_from_bytes_mut(&mut member_data)?;
+    let solution = Solution::new(args.digest, args.nonce);
+    if !is_member_nonce(member, &args.nonce) || !solution.is_valid(&proof.challenge) {
+        return Err(LuckycoinError::HashInvalid.into());
+    }
+
+    // 计算贡献的工作量
+    let difficulty = solution.to_hash().difficulty();
+    let work = calculate_work(difficulty, config.min_difficulty)?;
+
+    // 进入新轮次时先结算成员上一轮的工作量
+    settle_member(pool, member)?;
+
+    // 只记录成员本轮最好的解决方案
+    if work.gt(&member.work) {
+        pool.total_work = pool
+            .total_work
+            .checked_add(work.saturating_sub(member.work))
+            .ok_or(LuckycoinError::Overflow)?;
+        member.work = work;
+    }
+
+    // 记录矿池本轮最好的解决方案
+    if (difficulty as u64).gt(&pool.best_difficulty) {
+        pool.best_member = *member_info.key;
+        pool.best_digest = args.digest;
+        pool.best_nonce = args.nonce;
+        pool.best_difficulty = difficulty as u64;
+    }
+
+    Ok(())
+}
+
+/// 检查随机数是否属于给定成员。随机数的高 32 位是成员编号，低 32 位由成员自由搜索。
+pub fn is_member_nonce(member: &Member, nonce: &[u8; 8]) -> bool {
+    (u64::from_le_bytes(*nonce) >> 32).eq(&member.id)
+}
+
+/// 计算难度为 `difficulty` 的解决方案贡献的工作量。难度低于最低难度时返回错误。
+pub fn calculate_work(difficulty: u32, min_difficulty: u64) -> Result<u64, LuckycoinError> {
+    let normalized_difficulty = (difficulty as u64)
+        .checked_sub(min_difficulty)
+        .ok_or(LuckycoinError::HashTooEasy)?;
+    Ok(1u64 << normalized_difficulty.min(63))
+}
+
+#[cfg(test)]
+mod tests {
+    use luckycoin_api::{error::LuckycoinError, state::Member};
+    use steel::*;
+
+    use super::{calculate_work, is_member_nonce};
+
+    #[test]
+    fn test_calculate_work() {
+        assert_eq!(calculate_work(10, 10), Ok(1));
+        assert_eq!(calculate_work(13, 10), Ok(8));
+        assert_eq!(calculate_work(128, 10), Ok(1 << 63));
+        assert_eq!(calculate_work(9, 10), Err(LuckycoinError::HashTooEasy));
+        assert_eq!(calculate_work(10, u64::MAX), Err(LuckycoinError::HashTooEasy));
+    }
+
+    #[test]
+    fn test_is_member_nonce() {
+        let mut member = Member::zeroed();
+        member.id = 7;
+        let nonce = ((7u64 << 32) | 12_345).to_le_bytes();
+        assert!(is_member_nonce(&member, &nonce));
+
+        // 其他成员的随机数不能使用
+        let nonce = ((8u64 << 32) | 12_345).to_le_bytes();
+        assert!(!is_member_nonce(&member, &nonce));
+        member.id = 0;
+        assert!(is_member_nonce(&member, &12_345u64.to_le_bytes()));
+    }
+}
@@ -0,0 +1,25 @@
+use luckycoin_api::{loaders::*, state::Config};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+use steel::*;
+
+/// ProposeAdmin 记录一个新的管理员，新管理员需要通过 AcceptAdmin 确认转移。
+pub fn process_propose_admin(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer, new_admin_info, config_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    // 加载新的管理员账户，允许其为任何账户
+    load_any(new_admin_info, false)?;
+    // 加载配置账户，并确保签名者是当前管理员
+    load_config_with_admin(config_info, signer.key, true)?;
+
+    // 记录待处理的管理员。提议默认公钥即取消转移。
+    let mut config_data = config_info.data.borrow_mut();
+    let config = Config::try_from_bytes_mut(&mut config_data)?;
+    config.pending_admin = *new_admin_info.key;
+
+    Ok(())
+}
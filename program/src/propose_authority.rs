@@ -0,0 +1,25 @@
+use luckycoin_api::{loaders::*, state::Proof};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+use steel::*;
+
+/// ProposeAuthority 记录一个新的授权者，新授权者需要通过 AcceptAuthority 确认转移。
+pub fn process_propose_authority(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer, new_authority_info, proof_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    // 加载新的授权者账户，允许其为任何账户
+    load_any(new_authority_info, false)?;
+    // 加载证明账户，并确保签名者是当前授权者
+    load_proof(proof_info, signer.key, true)?;
+
+    // 记录待处理的授权者。提议默认公钥即取消转移。
+    let mut proof_data = proof_info.data.borrow_mut();
+    let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
+    proof.pending_authority = *new_authority_info.key;
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use luckycoin_api::{
+    instruction::Quote,
+    loaders::*,
+    reward::calculate_reward,
+    state::{Bus, Config, Proof},
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program::set_return_data, program_error::ProgramError, sysvar::Sysvar,
+};
+use steel::*;
+
+/// Quote 计算 proof 账户此时在给定 bus 上提交给定难度的哈希可以获得的奖励，并将结果写入返回数据。
+///
+/// 该指令只读，与挖矿指令使用相同的奖励计算，不包括幸运奖池。
+pub fn process_quote(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // 解析参数
+    let args = Quote::try_from_bytes(data)?;
+    let difficulty = u64::from_le_bytes(args.difficulty).min(u32::MAX as u64) as u32;
+
+    // 加载账户
+    let [config_info, proof_info, bus_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_config(config_info, false)?;
+    load_any_proof(proof_info, false)?;
+    load_any_bus(bus_info, false)?;
+
+    // 计算奖励
+    let config_data = config_info.data.borrow();
+    let config = Config::try_from_bytes(&config_data)?;
+    let proof_data = proof_info.data.borrow();
+    let proof = Proof::try_from_bytes(&proof_data)?;
+    let bus_data = bus_info.data.borrow();
+    let bus = Bus::try_from_bytes(&bus_data)?;
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+    let reward = calculate_reward(config, proof, bus, difficulty, clock.unix_timestamp);
+    set_return_data(reward.to_bytes());
+
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use luckycoin_api::{
+    consts::*,
+    cpi::emit_event,
+    error::LuckycoinError,
+    event::{CloseEvent, EventHeader, LuckycoinEvent},
+    loaders::*,
+    state::{Config, Proof},
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program_error::ProgramError, sysvar::Sysvar,
+};
+use steel::*;
+
+use crate::close::close_linked_accounts;
+
+/// Reap 关闭授权者长期未使用的 proof 账户，任何人都可以调用。
+///
+/// proof 账户的收益和质押本金必须为零，且最近一次挖矿和质押都早于配置的回收闲置时间。
+/// 调用者获得一小部分租金作为小费，其余租金退还给授权者。矿池的 proof 账户由矿池管理，不能被回收。
+/// 与 proof 账户关联的 miner set 和 session 账户必须排在最后一并提供，它们的租金全部退还给授权者。
+pub fn process_reap(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [
+        signer,
+        authority_info,
+        config_info,
+        proof_info,
+        event_authority_info,
+        luckycoin_program,
+        linked_infos @ ..
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_config(config_info, false)?;
+    load_proof(proof_info, authority_info.key, true)?;
+    load_event_authority(event_authority_info)?;
+    load_program(luckycoin_program, luckycoin_api::id())?;
+
+    // 验证 proof 账户可以回收。矿池的 proof 账户的授权者是本程序拥有的矿池账户。
+    let config_data = config_info.data.borrow();
+    let config = Config::try_from_bytes(&config_data)?;
+    let mut proof_data = proof_info.data.borrow_mut();
+    let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+    let authority_is_pool = authority_info.owner.eq(&luckycoin_api::id());
+    reap_check(config, proof, authority_is_pool, clock.unix_timestamp)?;
+
+    // 关闭所有关联账户
+    close_linked_accounts(proof_info.key, proof, linked_infos, authority_info)?;
+    let event = CloseEvent {
+        header: EventHeader::new(LuckycoinEvent::Close),
+        authority: proof.authority,
+        proof: *proof_info.key,
+        total_hashes: proof.total_hashes,
+        total_rewards: proof.total_rewards,
+    };
+
+    drop(proof_data);
+
+    // 将证明账户的数据将重新分配为零
+    proof_info.realloc(0, true)?;
+
+    // 将小费发送给调用者，其余的 lamports 退还给授权者
+    let lamports = proof_info.lamports();
+    let tip = reap_tip(lamports);
+    **signer.lamports.borrow_mut() += tip;
+    **authority_info.lamports.borrow_mut() += lamports.saturating_sub(tip);
+    **proof_info.lamports.borrow_mut() = 0;
+
+    // 记录关闭事件
+    emit_event(event_authority_info, luckycoin_program, event.to_bytes())?;
+
+    Ok(())
+}
+
+/// 验证 proof 账户在时间 `t` 可以被回收。
+///
+/// 回收未被禁用，proof 账户不属于矿池，收益和质押本金为零，且最近一次挖矿和质押都早于回收闲置时间。
+pub fn reap_check(
+    config: &Config,
+    proof: &Proof,
+    authority_is_pool: bool,
+    t: i64,
+) -> Result<(), LuckycoinError> {
+    let last_active_at = proof.last_hash_at.max(proof.last_stake_at);
+    if authority_is_pool
+        || config.reap_period.eq(&0)
+        || proof.earnings.gt(&0)
+        || proof.stake.gt(&0)
+        || last_active_at.saturating_add(config.reap_period).gt(&t)
+    {
+        return Err(LuckycoinError::ReapInvalid);
+    }
+    Ok(())
+}
+
+/// 计算回收 proof 账户时支付给调用者的小费。小费不超过账户的租金。
+pub fn reap_tip(lamports: u64) -> u64 {
+    ((lamports as u128) * (REAP_TIP_RATE as u128) / (BPS_DENOMINATOR as u128)) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use luckycoin_api::{
+        consts::*,
+        error::LuckycoinError,
+        state::{Config, Proof},
+    };
+    use steel::*;
+
+    use super::{reap_check, reap_tip};
+
+    fn setup() -> (Config, Proof, i64) {
+        let mut config = Config::zeroed();
+        config.reap_period = REAP_PERIOD;
+        let mut proof = Proof::zeroed();
+        proof.last_hash_at = 1_000;
+        proof.last_stake_at = 500;
+        (config, proof, 1_000 + REAP_PERIOD)
+    }
+
+    #[test]
+    fn test_reap_check_idle_period() {
+        let (config, mut proof, t) = setup();
+        assert_eq!(reap_check(&config, &proof, false, t), Ok(()));
+        assert_eq!(reap_check(&config, &proof, false, t - 1), Err(LuckycoinError::ReapInvalid));
+
+        // 最近一次质押同样计入闲置时间
+        proof.last_stake_at = 2_000;
+        assert_eq!(reap_check(&config, &proof, false, t), Err(LuckycoinError::ReapInvalid));
+    }
+
+    #[test]
+    fn test_reap_check_disabled() {
+        let (mut config, proof, t) = setup();
+        config.reap_period = 0;
+        assert_eq!(reap_check(&config, &proof, false, t), Err(LuckycoinError::ReapInvalid));
+    }
+
+    #[test]
+    fn test_reap_check_nonzero_balance() {
+        let (config, mut proof, t) = setup();
+        proof.earnings = 1;
+        assert_eq!(reap_check(&config, &proof, false, t), Err(LuckycoinError::ReapInvalid));
+        proof.earnings = 0;
+        proof.stake = 1;
+        assert_eq!(reap_check(&config, &proof, false, t), Err(LuckycoinError::ReapInvalid));
+    }
+
+    #[test]
+    fn test_reap_check_pool() {
+        let (config, proof, t) = setup();
+        assert_eq!(reap_check(&config, &proof, true, t), Err(LuckycoinError::ReapInvalid));
+    }
+
+    #[test]
+    fn test_reap_tip() {
+        assert_eq!(reap_tip(0), 0);
+        assert_eq!(reap_tip(1_000_000), 1_000_000 * REAP_TIP_RATE / BPS_DENOMINATOR);
+
+        // 大额租金不会溢出，小费始终小于租金
+        let tip = reap_tip(u64::MAX);
+        assert_eq!(tip as u128, u64::MAX as u128 * REAP_TIP_RATE as u128 / 10_000);
+        assert!(tip < u64::MAX);
+    }
+}
@@ -0,0 +1,106 @@
+use luckycoin_api::{
+    error::LuckycoinError,
+    loaders::*,
+    state::{Bus, Config},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+use steel::*;
+
+/// Rebalance 在周期内将剩余的奖励平均分配到所有 bus 上，任何人都可以调用。
+///
+/// 当某个 bus 提前耗尽时，选择该 bus 的矿工将得不到奖励。重新平衡可以让剩余的奖励
+/// 在所有 bus 之间保持均匀，而不改变本周期的总发行量。幸运奖池和理论奖励不受影响。
+///
+/// 只有至少一个 bus 已经耗尽、并且剩余奖励足以让每个 bus 至少分到 1 个单位时才能重新平衡。
+/// 重新平衡之后所有 bus 都有剩余奖励，因此在下一个 bus 被挖空之前无法再次调用。
+pub fn process_rebalance(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // 加载账户，bus 账户按 id 顺序排在最后
+    let [signer, config_info, busses @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_config(config_info, false)?;
+
+    // 验证所有 bus 都已按顺序提供
+    let config_data = config_info.data.borrow();
+    let config = Config::try_from_bytes(&config_data)?;
+    if busses.is_empty() || (busses.len() as u64).ne(&config.bus_count) {
+        return Err(LuckycoinError::BusCountInvalid.into());
+    }
+    for (i, bus_info) in busses.iter().enumerate() {
+        load_bus(bus_info, i as u64, true)?;
+    }
+
+    // 计算重新平衡后每个 bus 的奖励
+    let mut rewards = Vec::with_capacity(busses.len());
+    for bus_info in busses.iter() {
+        let bus_data = bus_info.data.borrow();
+        let bus = Bus::try_from_bytes(&bus_data)?;
+        rewards.push(bus.rewards);
+    }
+    let rewards = rebalance_rewards(&rewards)?;
+
+    // 更新 bus 的剩余奖励
+    for (bus_info, amount) in busses.iter().zip(rewards) {
+        let mut bus_data = bus_info.data.borrow_mut();
+        let bus = Bus::try_from_bytes_mut(&mut bus_data)?;
+        bus.rewards = amount;
+    }
+
+    Ok(())
+}
+
+/// 将所有 bus 的剩余奖励平均分配，余数分给 id 较小的 bus。
+///
+/// 没有耗尽的 bus，或者剩余奖励少于 bus 数量时返回错误。
+pub fn rebalance_rewards(rewards: &[u64]) -> Result<Vec<u64>, LuckycoinError> {
+    let count = rewards.len() as u64;
+    let total = rewards
+        .iter()
+        .try_fold(0u64, |acc, r| acc.checked_add(*r))
+        .ok_or(LuckycoinError::Overflow)?;
+    if !rewards.contains(&0) || total.lt(&count) {
+        return Err(LuckycoinError::RebalanceInvalid);
+    }
+    let share = total / count;
+    let remainder = total % count;
+    Ok((0..count)
+        .map(|i| if i.lt(&remainder) { share + 1 } else { share })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use luckycoin_api::error::LuckycoinError;
+
+    use super::rebalance_rewards;
+
+    #[test]
+    fn test_rebalance_rewards() {
+        assert_eq!(rebalance_rewards(&[0, 10, 21]), Ok(vec![11, 10, 10]));
+        assert_eq!(rebalance_rewards(&[0, 0, 3]), Ok(vec![1, 1, 1]));
+    }
+
+    #[test]
+    fn test_rebalance_rewards_none_drained() {
+        // 所有 bus 都有剩余奖励时不能重新平衡
+        assert_eq!(rebalance_rewards(&[1, 10, 21]), Err(LuckycoinError::RebalanceInvalid));
+    }
+
+    #[test]
+    fn test_rebalance_rewards_insufficient() {
+        // 剩余奖励不足以分配到每个 bus
+        assert_eq!(rebalance_rewards(&[0, 0, 2]), Err(LuckycoinError::RebalanceInvalid));
+        assert_eq!(rebalance_rewards(&[0, 0, 0]), Err(LuckycoinError::RebalanceInvalid));
+    }
+
+    #[test]
+    fn test_rebalance_rewards_once_per_drain() {
+        // 重新平衡之后必须等到下一个 bus 被挖空
+        let rewards = rebalance_rewards(&[0, 5, 7]).unwrap();
+        assert_eq!(rewards, vec![4, 4, 4]);
+        assert_eq!(rebalance_rewards(&rewards), Err(LuckycoinError::RebalanceInvalid));
+    }
+}
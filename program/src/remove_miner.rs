@@ -0,0 +1,31 @@
+use luckycoin_api::{error::LuckycoinError, loaders::*, state::MinerSet};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use steel::*;
+
+/// RemoveMiner 撤销一个矿工密钥使用签名者的 proof 账户挖矿的授权。
+pub fn process_remove_miner(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // 加载账户
+    let [signer, miner_info, miner_set_info, proof_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any(miner_info, false)?;
+    load_proof(proof_info, signer.key, false)?;
+    load_miner_set(miner_set_info, proof_info.key, true)?;
+
+    // 清空该密钥所在的位置
+    let mut miner_set_data = miner_set_info.data.borrow_mut();
+    let miner_set = MinerSet::try_from_bytes_mut(&mut miner_set_data)?;
+    let slot = miner_set
+        .miners
+        .iter()
+        .position(|key| key.eq(miner_info.key))
+        .ok_or(LuckycoinError::MinerNotAuthorized)?;
+    miner_set.miners[slot] = Pubkey::default();
+    miner_set.expires_at[slot] = 0;
+
+    Ok(())
+}
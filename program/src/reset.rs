@@ -1,50 +1,60 @@
 use luckycoin_api::{
     consts::*,
+    cpi::{emit_event, mint_to_checked_signed},
     error::LuckycoinError,
+    event::{EventHeader, LuckycoinEvent, ResetEvent},
     loaders::*,
-    state::{Bus, Config},
+    state::{Bus, Config, Treasury},
 };
 use solana_program::{
     account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
-    program_error::ProgramError, program_pack::Pack, sysvar::Sysvar,
+    program_error::ProgramError, sysvar::Sysvar,
 };
-use spl_token::state::Mint;
+use spl_token_2022::{extension::StateWithExtensions, state::Mint};
 use steel::*;
 
 /// Reset tops up the bus balances, updates the base reward rate, and sets up the ORE program for the next epoch.
+///
+/// Anyone can call reset. The beneficiary token account, which must be owned by the signer, is paid the
+/// configured bounty as a share of the tokens minted for the epoch, so keepers are rewarded for keeping the
+/// epochs moving without inflating issuance. Only the first reset of an epoch does any work, so only it is
+/// paid.
 pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
-    // Load accounts.
-    let [signer, bus_0_info, bus_1_info, bus_2_info, bus_3_info, bus_4_info, bus_5_info, bus_6_info, bus_7_info, config_info, mint_info, treasury_info, treasury_tokens_info, token_program] =
+    // Load accounts. The bus accounts are passed last, one for each bus created so far.
+    let [signer, beneficiary_info, config_info, mint_info, treasury_info, treasury_tokens_info, token_program, event_authority_info, luckycoin_program, busses @ ..] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     load_signer(signer)?;
-    load_bus(bus_0_info, 0, true)?;
-    load_bus(bus_1_info, 1, true)?;
-    load_bus(bus_2_info, 2, true)?;
-    load_bus(bus_3_info, 3, true)?;
-    load_bus(bus_4_info, 4, true)?;
-    load_bus(bus_5_info, 5, true)?;
-    load_bus(bus_6_info, 6, true)?;
-    load_bus(bus_7_info, 7, true)?;
+    load_token_account(beneficiary_info, Some(signer.key), &MINT_ADDRESS, true)?;
     load_config(config_info, true)?;
     load_mint(mint_info, MINT_ADDRESS, true)?;
     load_treasury(treasury_info, true)?;
     load_treasury_tokens(treasury_tokens_info, true)?;
-    load_program(token_program, spl_token::id())?;
-    let busses: [&AccountInfo; BUS_COUNT] = [
-        bus_0_info, bus_1_info, bus_2_info, bus_3_info, bus_4_info, bus_5_info, bus_6_info,
-        bus_7_info,
-    ];
+    load_token_program(token_program)?;
+    load_event_authority(event_authority_info)?;
+    load_program(luckycoin_program, luckycoin_api::id())?;
 
     // Validate enough time has passed since the last reset.
     let mut config_data = config_info.data.borrow_mut();
     let config = Config::try_from_bytes_mut(&mut config_data)?;
+    if config.is_paused(PAUSE_RESET) {
+        return Err(LuckycoinError::Paused.into());
+    }
+
+    // Validate every bus is present and in order.
+    if (busses.len() as u64).ne(&config.bus_count) {
+        return Err(LuckycoinError::BusCountInvalid.into());
+    }
+    for (i, bus_info) in busses.iter().enumerate() {
+        load_bus(bus_info, i as u64, true)?;
+    }
+
     let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
     if config
         .last_reset_at
-        .saturating_add(EPOCH_DURATION)
+        .saturating_add(config.epoch_duration)
         .gt(&clock.unix_timestamp)
     {
         return Ok(());
@@ -53,14 +63,23 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
     // Update timestamp.
     config.last_reset_at = clock.unix_timestamp;
 
-    // Reset bus accounts and calculate actual rewards mined since last reset.
-    let mut total_remaining_rewards = 0u64;
+    // Look up the scheduled target for the new epoch. Each bus is allocated the full target.
+    let target_rewards = calculate_scheduled_target(
+        config.emission_curve,
+        config.emission_interval,
+        config.emission_decay,
+        config.epoch_count,
+    );
+    config.epoch_count = config.epoch_count.saturating_add(1);
+
+    // Calculate actual rewards mined since last reset.
+    let mut needed = 0u64;
     let mut total_theoretical_rewards = 0u64;
     let mut top_balance = 0u64;
-    for i in 0..BUS_COUNT {
+    for bus_info in busses.iter() {
         // Parse bus account.
-        let mut bus_data = busses[i].data.borrow_mut();
-        let bus = Bus::try_from_bytes_mut(&mut bus_data)?;
+        let bus_data = bus_info.data.borrow();
+        let bus = Bus::try_from_bytes(&bus_data)?;
 
         // Track top balance.
         if bus.top_balance.gt(&top_balance) {
@@ -68,77 +87,236 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
         }
 
         // Track accumulators.
-        total_remaining_rewards = total_remaining_rewards.saturating_add(bus.rewards);
+        needed = needed.saturating_add(target_rewards.saturating_sub(bus.rewards));
         total_theoretical_rewards =
             total_theoretical_rewards.saturating_add(bus.theoretical_rewards);
-
-        // Reset bus account for new epoch.
-        bus.rewards = BUS_EPOCH_REWARDS;
-        bus.theoretical_rewards = 0;
-        bus.top_balance = 0;
     }
-    let total_epoch_rewards = MAX_EPOCH_REWARDS.saturating_sub(total_remaining_rewards);
 
     // Update global top balance.
     config.top_balance = top_balance;
 
     // Update base reward rate for next epoch.
-    config.base_reward_rate =
-        calculate_new_reward_rate(config.base_reward_rate, total_theoretical_rewards);
+    config.base_reward_rate = calculate_new_reward_rate(
+        config.base_reward_rate,
+        total_theoretical_rewards,
+        target_rewards,
+        config.smoothing_factor,
+    );
 
     // If base reward rate is too low, increment min difficulty by 1 and double base reward rate.
-    if config.base_reward_rate.le(&BASE_REWARD_RATE_MIN_THRESHOLD) {
+    if config
+        .base_reward_rate
+        .le(&config.base_reward_rate_min_threshold)
+    {
         config.min_difficulty = config.min_difficulty.checked_add(1).unwrap();
         config.base_reward_rate = config.base_reward_rate.checked_mul(2).unwrap();
     }
 
     // If base reward rate is too high, decrement min difficulty by 1 and halve base reward rate.
-    if config.base_reward_rate.ge(&BASE_REWARD_RATE_MAX_THRESHOLD) && config.min_difficulty.gt(&1) {
+    if config
+        .base_reward_rate
+        .ge(&config.base_reward_rate_max_threshold)
+        && config.min_difficulty.gt(&1)
+    {
         config.min_difficulty = config.min_difficulty.checked_sub(1).unwrap();
         config.base_reward_rate = config.base_reward_rate.checked_div(2).unwrap();
     }
 
-    // Max supply check.
-    let mint = Mint::unpack(&mint_info.data.borrow()).expect("Failed to parse mint");
-    if mint.supply.ge(&MAX_SUPPLY) {
-        return Err(LuckycoinError::MaxSupply.into());
+    // Calculate the funding needed to top up every bus to its full allocation. Once the max supply is
+    // reached, new tokens are limited to the tail emission and the rest of the bus funding is drawn from
+    // the reward pool. The bounty is a share of the newly minted tokens, taken before the busses are funded.
+    let supply = StateWithExtensions::<Mint>::unpack(&mint_info.data.borrow())?
+        .base
+        .supply;
+    let mintable = MAX_SUPPLY.saturating_sub(supply).max(config.tail_emission);
+    let (minted_for_busses, drawn, bounty) =
+        calculate_funding(needed, mintable, config.reward_pool, config.reset_bounty);
+    let amount = minted_for_busses
+        .checked_add(bounty)
+        .ok_or(LuckycoinError::Overflow)?;
+    config.reward_pool = config
+        .reward_pool
+        .checked_sub(drawn)
+        .ok_or(LuckycoinError::Overflow)?;
+    let funded = minted_for_busses
+        .checked_add(drawn)
+        .ok_or(LuckycoinError::Overflow)?;
+
+    // Reset bus accounts for the new epoch. If the funding falls short, every bus is topped up
+    // pro-rata. Rewards left above a shrinking allocation and the rounding dust are returned to the reward pool.
+    let mut total_top_up = 0u64;
+    let mut total_excess = 0u64;
+    let mut total_liabilities = 0u64;
+    for bus_info in busses.iter() {
+        // Parse bus account.
+        let mut bus_data = bus_info.data.borrow_mut();
+        let bus = Bus::try_from_bytes_mut(&mut bus_data)?;
+
+        // Top up the bus.
+        let top_up = if needed.gt(&0) {
+            let top_up = (target_rewards.saturating_sub(bus.rewards) as u128)
+                .checked_mul(funded as u128)
+                .and_then(|top_up| top_up.checked_div(needed as u128))
+                .ok_or(LuckycoinError::Overflow)?;
+            u64::try_from(top_up).or(Err(LuckycoinError::Overflow))?
+        } else {
+            0
+        };
+        let excess = bus.rewards.saturating_sub(target_rewards);
+        let allocation = bus
+            .rewards
+            .checked_sub(excess)
+            .and_then(|rewards| rewards.checked_add(top_up))
+            .ok_or(LuckycoinError::Overflow)?;
+        total_top_up = total_top_up
+            .checked_add(top_up)
+            .ok_or(LuckycoinError::Overflow)?;
+        total_excess = total_excess
+            .checked_add(excess)
+            .ok_or(LuckycoinError::Overflow)?;
+
+        // Carve the jackpot slice out of the bus allocation.
+        let jackpot_slice = allocation
+            .saturating_mul(config.jackpot_rate)
+            .saturating_div(BPS_DENOMINATOR);
+        bus.jackpot = bus.jackpot.saturating_add(jackpot_slice);
+
+        // Reset bus account for new epoch.
+        bus.rewards = allocation.saturating_sub(jackpot_slice);
+        bus.theoretical_rewards = 0;
+        bus.top_balance = 0;
+
+        // Settle the rewards credited by this bus into the treasury liabilities.
+        total_liabilities = total_liabilities
+            .checked_add(bus.liabilities)
+            .ok_or(LuckycoinError::Overflow)?;
+        bus.liabilities = 0;
     }
+    {
+        let mut treasury_data = treasury_info.data.borrow_mut();
+        let treasury = Treasury::try_from_bytes_mut(&mut treasury_data)?;
+        treasury.total_credited = treasury
+            .total_credited
+            .checked_add(total_liabilities)
+            .ok_or(LuckycoinError::Overflow)?;
+    }
+    config.reward_pool = config
+        .reward_pool
+        .checked_add(funded.saturating_sub(total_top_up))
+        .and_then(|reward_pool| reward_pool.checked_add(total_excess))
+        .ok_or(LuckycoinError::Overflow)?;
 
     // Fund the treasury token account.
-    let amount = MAX_SUPPLY
-        .saturating_sub(mint.supply)
-        .min(total_epoch_rewards);
-    solana_program::program::invoke_signed(
-        &spl_token::instruction::mint_to(
-            &spl_token::id(),
-            mint_info.key,
-            treasury_tokens_info.key,
-            treasury_info.key,
-            &[treasury_info.key],
-            amount,
-        )?,
-        &[
-            token_program.clone(),
-            mint_info.clone(),
-            treasury_tokens_info.clone(),
-            treasury_info.clone(),
-        ],
+    mint_to_checked_signed(
+        mint_info,
+        treasury_tokens_info,
+        treasury_info,
+        token_program,
+        minted_for_busses,
         &[&[TREASURY, &[TREASURY_BUMP]]],
     )?;
 
+    // Pay the bounty to the beneficiary.
+    if bounty.gt(&0) {
+        mint_to_checked_signed(
+            mint_info,
+            beneficiary_info,
+            treasury_info,
+            token_program,
+            bounty,
+            &[&[TREASURY, &[TREASURY_BUMP]]],
+        )?;
+    }
+
+    // Log the reset.
+    let event = ResetEvent {
+        header: EventHeader::new(LuckycoinEvent::Reset),
+        base_reward_rate: config.base_reward_rate,
+        min_difficulty: config.min_difficulty,
+        minted: amount,
+        top_balance: config.top_balance,
+        beneficiary: *beneficiary_info.key,
+        bounty,
+        timing: clock.unix_timestamp,
+    };
+    emit_event(event_authority_info, luckycoin_program, event.to_bytes())?;
+
     Ok(())
 }
 
+/// This function splits the epoch funding into the tokens minted for the busses, the tokens drawn from
+/// the reward pool and the bounty, in that order.
+///
+/// Up to `mintable` new tokens are minted for the epoch and any shortfall is drawn from the reward pool.
+/// The bounty is carved out of the minted tokens before the busses are funded, so the total issuance
+/// never exceeds what the busses need.
+pub(crate) fn calculate_funding(
+    needed: u64,
+    mintable: u64,
+    reward_pool: u64,
+    reset_bounty: u64,
+) -> (u64, u64, u64) {
+    let minted = needed.min(mintable);
+    let drawn = needed.saturating_sub(minted).min(reward_pool);
+    let bounty = reset_bounty.min(minted);
+    (minted.saturating_sub(bounty), drawn, bounty)
+}
+
+/// This function returns the target rewards for the given epoch under the configured emission curve.
+///
+/// - Flat: every epoch targets TARGET_EPOCH_REWARDS.
+/// - Halving: the target halves every `interval` epochs, as in Bitcoin.
+/// - Decay: the target is multiplied by `decay` basis points every `interval` epochs.
+pub(crate) fn calculate_scheduled_target(
+    curve: u64,
+    interval: u64,
+    decay: u64,
+    epoch_count: u64,
+) -> u64 {
+    // Count the elapsed steps of the schedule. An interval of zero disables the schedule.
+    let steps = epoch_count.checked_div(interval).unwrap_or(0);
+    match curve {
+        EMISSION_CURVE_HALVING => TARGET_EPOCH_REWARDS
+            .checked_shr(steps.min(64) as u32)
+            .unwrap_or(0),
+        EMISSION_CURVE_DECAY => {
+            // Raise the decay factor to the number of steps by squaring in fixed point.
+            const SCALE: u128 = 1_000_000_000_000;
+            let mut factor = SCALE;
+            let mut base = (decay.min(BPS_DENOMINATOR) as u128)
+                .saturating_mul(SCALE)
+                .saturating_div(BPS_DENOMINATOR as u128);
+            let mut n = steps;
+            while n.gt(&0) && factor.gt(&0) {
+                if n & 1 == 1 {
+                    factor = factor.saturating_mul(base).saturating_div(SCALE);
+                }
+                base = base.saturating_mul(base).saturating_div(SCALE);
+                n >>= 1;
+            }
+            (TARGET_EPOCH_REWARDS as u128)
+                .saturating_mul(factor)
+                .saturating_div(SCALE) as u64
+        }
+        _ => TARGET_EPOCH_REWARDS,
+    }
+}
+
 /// This function calculates what the new reward rate should be based on how many total rewards
 /// were mined in the prior epoch. The math is largely identitical to function used by the Bitcoin
 /// network to update the difficulty between each epoch.
 ///
 /// new_rate = current_rate * (target_rewards / actual_rewards)
 ///
-/// The new rate is then smoothed by a constant factor to avoid large fluctuations. In Ore's case,
-/// the epochs are short (60 seconds) so a smoothing factor of 2 has been chosen. That is, the reward rate
+/// The new rate is then smoothed by the configured factor to avoid large fluctuations. In Ore's case,
+/// the epochs are short (60 seconds) so a default smoothing factor of 2 has been chosen. That is, the reward rate
 /// can at most double or halve from one epoch to the next.
-pub(crate) fn calculate_new_reward_rate(current_rate: u64, epoch_rewards: u64) -> u64 {
+pub(crate) fn calculate_new_reward_rate(
+    current_rate: u64,
+    epoch_rewards: u64,
+    target_rewards: u64,
+    smoothing_factor: u64,
+) -> u64 {
     // Avoid division by zero. Leave the reward rate unchanged, if detected.
     if epoch_rewards.eq(&0) {
         return current_rate;
@@ -146,41 +324,47 @@ pub(crate) fn calculate_new_reward_rate(current_rate: u64, epoch_rewards: u64) -
 
     // Calculate new reward rate.
     let new_rate = (current_rate as u128)
-        .saturating_mul(TARGET_EPOCH_REWARDS as u128)
+        .saturating_mul(target_rewards as u128)
         .saturating_div(epoch_rewards as u128) as u64;
 
     // Smooth reward rate so it cannot change by more than a constant factor from one epoch to the next.
-    let new_rate_min = current_rate.saturating_div(SMOOTHING_FACTOR);
-    let new_rate_max = current_rate.saturating_mul(SMOOTHING_FACTOR);
+    let new_rate_min = current_rate.saturating_div(smoothing_factor);
+    let new_rate_max = current_rate.saturating_mul(smoothing_factor);
     let new_rate_smoothed = new_rate.min(new_rate_max).max(new_rate_min);
 
-    // Prevent reward rate from dropping below 1 or exceeding BUS_EPOCH_REWARDS and return.
-    new_rate_smoothed.max(1).min(BUS_EPOCH_REWARDS)
+    // Prevent reward rate from exceeding the per-bus allocation or dropping below 1 and return.
+    new_rate_smoothed.min(target_rewards).max(1)
 }
 
 #[cfg(test)]
 mod tests {
-    use rand::{distributions::Uniform, Rng};
-    use luckycoin_api::consts::{BASE_REWARD_RATE_MIN_THRESHOLD, BUS_EPOCH_REWARDS, MAX_EPOCH_REWARDS, SMOOTHING_FACTOR, TARGET_EPOCH_REWARDS};
-    use crate::calculate_new_reward_rate;
+    use crate::{calculate_funding, calculate_new_reward_rate, calculate_scheduled_target};
     use luckycoin_api::consts::{
-        BASE_REWARD_RATE_MIN_THRESHOLD, BUS_EPOCH_REWARDS, MAX_EPOCH_REWARDS, SMOOTHING_FACTOR,
+        BASE_REWARD_RATE_MIN_THRESHOLD, BPS_DENOMINATOR, BUS_EPOCH_REWARDS, EMISSION_CURVE_DECAY,
+        EMISSION_CURVE_FLAT, EMISSION_CURVE_HALVING, MAX_EPOCH_REWARDS, SMOOTHING_FACTOR,
         TARGET_EPOCH_REWARDS,
     };
+    use rand::{distributions::Uniform, Rng};
 
     const FUZZ_SIZE: u64 = 10_000;
 
     #[test]
     fn test_calculate_new_reward_rate_target() {
         let current_rate = 1000;
-        let new_rate = calculate_new_reward_rate(current_rate, TARGET_EPOCH_REWARDS);
+        let new_rate = calculate_new_reward_rate(
+            current_rate,
+            TARGET_EPOCH_REWARDS,
+            TARGET_EPOCH_REWARDS,
+            SMOOTHING_FACTOR,
+        );
         assert!(new_rate.eq(&current_rate));
     }
 
     #[test]
     fn test_calculate_new_reward_rate_div_by_zero() {
         let current_rate = 1000;
-        let new_rate = calculate_new_reward_rate(current_rate, 0);
+        let new_rate =
+            calculate_new_reward_rate(current_rate, 0, TARGET_EPOCH_REWARDS, SMOOTHING_FACTOR);
         assert!(new_rate.eq(&current_rate));
     }
 
@@ -190,6 +374,8 @@ mod tests {
         let new_rate = calculate_new_reward_rate(
             current_rate,
             TARGET_EPOCH_REWARDS.saturating_add(1_000_000_000),
+            TARGET_EPOCH_REWARDS,
+            SMOOTHING_FACTOR,
         );
         assert!(new_rate.lt(&current_rate));
     }
@@ -197,7 +383,12 @@ mod tests {
     #[test]
     fn test_calculate_new_reward_rate_lower_edge() {
         let current_rate = BASE_REWARD_RATE_MIN_THRESHOLD;
-        let new_rate = calculate_new_reward_rate(current_rate, TARGET_EPOCH_REWARDS + 1);
+        let new_rate = calculate_new_reward_rate(
+            current_rate,
+            TARGET_EPOCH_REWARDS + 1,
+            TARGET_EPOCH_REWARDS,
+            SMOOTHING_FACTOR,
+        );
         assert!(new_rate.lt(&current_rate));
     }
 
@@ -208,7 +399,12 @@ mod tests {
             let current_rate: u64 = rng.sample(Uniform::new(1, BUS_EPOCH_REWARDS));
             let actual_rewards: u64 =
                 rng.sample(Uniform::new(TARGET_EPOCH_REWARDS, MAX_EPOCH_REWARDS));
-            let new_rate = calculate_new_reward_rate(current_rate, actual_rewards);
+            let new_rate = calculate_new_reward_rate(
+                current_rate,
+                actual_rewards,
+                TARGET_EPOCH_REWARDS,
+                SMOOTHING_FACTOR,
+            );
             assert!(new_rate.lt(&current_rate));
         }
     }
@@ -219,6 +415,8 @@ mod tests {
         let new_rate = calculate_new_reward_rate(
             current_rate,
             TARGET_EPOCH_REWARDS.saturating_sub(1_000_000_000),
+            TARGET_EPOCH_REWARDS,
+            SMOOTHING_FACTOR,
         );
         assert!(new_rate.gt(&current_rate));
     }
@@ -229,7 +427,12 @@ mod tests {
         for _ in 0..FUZZ_SIZE {
             let current_rate: u64 = rng.sample(Uniform::new(1, BUS_EPOCH_REWARDS));
             let actual_rewards: u64 = rng.sample(Uniform::new(1, TARGET_EPOCH_REWARDS));
-            let new_rate = calculate_new_reward_rate(current_rate, actual_rewards);
+            let new_rate = calculate_new_reward_rate(
+                current_rate,
+                actual_rewards,
+                TARGET_EPOCH_REWARDS,
+                SMOOTHING_FACTOR,
+            );
             assert!(new_rate.gt(&current_rate));
         }
     }
@@ -237,26 +440,133 @@ mod tests {
     #[test]
     fn test_calculate_new_reward_rate_max_smooth() {
         let current_rate = 1000;
-        let new_rate = calculate_new_reward_rate(current_rate, 1);
+        let new_rate =
+            calculate_new_reward_rate(current_rate, 1, TARGET_EPOCH_REWARDS, SMOOTHING_FACTOR);
         assert!(new_rate.eq(&current_rate.saturating_mul(SMOOTHING_FACTOR)));
     }
 
     #[test]
     fn test_calculate_new_reward_rate_min_smooth() {
         let current_rate = 1000;
-        let new_rate = calculate_new_reward_rate(current_rate, u64::MAX);
+        let new_rate = calculate_new_reward_rate(
+            current_rate,
+            u64::MAX,
+            TARGET_EPOCH_REWARDS,
+            SMOOTHING_FACTOR,
+        );
         assert!(new_rate.eq(&current_rate.saturating_div(SMOOTHING_FACTOR)));
     }
 
     #[test]
     fn test_calculate_new_reward_rate_max_inputs() {
-        let new_rate = calculate_new_reward_rate(BUS_EPOCH_REWARDS, MAX_EPOCH_REWARDS);
+        let new_rate = calculate_new_reward_rate(
+            BUS_EPOCH_REWARDS,
+            MAX_EPOCH_REWARDS,
+            TARGET_EPOCH_REWARDS,
+            SMOOTHING_FACTOR,
+        );
         assert!(new_rate.eq(&BUS_EPOCH_REWARDS.saturating_div(SMOOTHING_FACTOR)));
     }
 
     #[test]
     fn test_calculate_new_reward_rate_min_inputs() {
-        let new_rate = calculate_new_reward_rate(1, 1);
+        let new_rate = calculate_new_reward_rate(1, 1, TARGET_EPOCH_REWARDS, SMOOTHING_FACTOR);
         assert!(new_rate.eq(&1u64.saturating_mul(SMOOTHING_FACTOR)));
     }
+
+    #[test]
+    fn test_calculate_new_reward_rate_follows_target() {
+        let current_rate = 1000;
+        let target_rewards = TARGET_EPOCH_REWARDS / 2;
+        let new_rate = calculate_new_reward_rate(
+            current_rate,
+            TARGET_EPOCH_REWARDS,
+            target_rewards,
+            SMOOTHING_FACTOR,
+        );
+        assert!(new_rate.eq(&current_rate.saturating_div(2)));
+    }
+
+    #[test]
+    fn test_calculate_new_reward_rate_capped_by_target() {
+        let new_rate = calculate_new_reward_rate(1000, 1, 100, SMOOTHING_FACTOR);
+        assert!(new_rate.eq(&100));
+    }
+
+    #[test]
+    fn test_calculate_scheduled_target_flat() {
+        for epoch_count in [0, 1, 1_000, u64::MAX] {
+            let target = calculate_scheduled_target(EMISSION_CURVE_FLAT, 100, 5_000, epoch_count);
+            assert!(target.eq(&TARGET_EPOCH_REWARDS));
+        }
+    }
+
+    #[test]
+    fn test_calculate_scheduled_target_halving() {
+        let target =
+            |epoch_count| calculate_scheduled_target(EMISSION_CURVE_HALVING, 100, 0, epoch_count);
+        assert!(target(0).eq(&TARGET_EPOCH_REWARDS));
+        assert!(target(99).eq(&TARGET_EPOCH_REWARDS));
+        assert!(target(100).eq(&TARGET_EPOCH_REWARDS.saturating_div(2)));
+        assert!(target(250).eq(&TARGET_EPOCH_REWARDS.saturating_div(4)));
+        assert!(target(100 * 64).eq(&0));
+        assert!(target(u64::MAX).eq(&0));
+    }
+
+    #[test]
+    fn test_calculate_scheduled_target_decay() {
+        let target = |decay, epoch_count| {
+            calculate_scheduled_target(EMISSION_CURVE_DECAY, 10, decay, epoch_count)
+        };
+        assert!(target(9_000, 0).eq(&TARGET_EPOCH_REWARDS));
+        assert!(target(9_000, 10).eq(&(TARGET_EPOCH_REWARDS / 10 * 9)));
+        assert!(target(9_000, 20).eq(&(TARGET_EPOCH_REWARDS / 100 * 81)));
+        assert!(target(5_000, 30).eq(&TARGET_EPOCH_REWARDS.saturating_div(8)));
+        assert!(target(BPS_DENOMINATOR, u64::MAX).eq(&TARGET_EPOCH_REWARDS));
+        assert!(target(9_000, u64::MAX).eq(&0));
+    }
+
+    #[test]
+    fn test_calculate_scheduled_target_decay_monotonic() {
+        let mut prev = TARGET_EPOCH_REWARDS;
+        for epoch_count in 0..1_000 {
+            let target = calculate_scheduled_target(EMISSION_CURVE_DECAY, 1, 9_900, epoch_count);
+            assert!(target.le(&prev));
+            prev = target;
+        }
+    }
+
+    #[test]
+    fn test_calculate_scheduled_target_zero_interval() {
+        let target = calculate_scheduled_target(EMISSION_CURVE_HALVING, 0, 0, 1_000);
+        assert!(target.eq(&TARGET_EPOCH_REWARDS));
+    }
+
+    #[test]
+    fn test_calculate_funding_before_max_supply() {
+        // The bounty is a share of the epoch's minted tokens, not minted on top of them.
+        let (minted_for_busses, drawn, bounty) = calculate_funding(1_000, 10_000, 500, 10);
+        assert_eq!((minted_for_busses, drawn, bounty), (990, 0, 10));
+        assert_eq!(minted_for_busses + bounty, 1_000);
+    }
+
+    #[test]
+    fn test_calculate_funding_bounty_capped() {
+        // The bounty never exceeds the tokens minted for the epoch.
+        assert_eq!(calculate_funding(5, 10_000, 500, 10), (0, 0, 5));
+    }
+
+    #[test]
+    fn test_calculate_funding_tail_emission() {
+        // The shortfall is drawn from the reward pool and the bounty comes out of the tail emission.
+        assert_eq!(calculate_funding(1_000, 100, 500, 10), (90, 500, 10));
+        assert_eq!(calculate_funding(1_000, 100, 2_000, 10), (90, 900, 10));
+    }
+
+    #[test]
+    fn test_calculate_funding_nothing_needed() {
+        // Nothing is minted, so no bounty is paid.
+        assert_eq!(calculate_funding(0, 100, 500, 10), (0, 0, 0));
+        assert_eq!(calculate_funding(0, 0, 500, 10), (0, 0, 0));
+    }
 }
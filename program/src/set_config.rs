@@ -0,0 +1,148 @@
+use luckycoin_api::{
+    consts::*,
+    error::LuckycoinError,
+    instruction::SetConfig,
+    loaders::*,
+    state::Config,
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+use steel::*;
+
+/// SetConfig 允许管理员在运行时调整程序的经济参数。管理员本身通过 ProposeAdmin 和 AcceptAdmin 转移。
+pub fn process_set_config(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // 解析参数
+    let args = SetConfig::try_from_bytes(data)?;
+    let epoch_duration = i64::from_le_bytes(args.epoch_duration);
+    let tolerance = i64::from_le_bytes(args.tolerance);
+    let base_reward_rate_min_threshold = u64::from_le_bytes(args.base_reward_rate_min_threshold);
+    let base_reward_rate_max_threshold = u64::from_le_bytes(args.base_reward_rate_max_threshold);
+    let smoothing_factor = u64::from_le_bytes(args.smoothing_factor);
+    let max_hash_reward = u64::from_le_bytes(args.max_hash_reward);
+    let unstake_cooldown = i64::from_le_bytes(args.unstake_cooldown);
+    let jackpot_rate = u64::from_le_bytes(args.jackpot_rate);
+    let jackpot_difficulty = u64::from_le_bytes(args.jackpot_difficulty);
+    let jackpot_odds = u64::from_le_bytes(args.jackpot_odds);
+    let tail_emission = u64::from_le_bytes(args.tail_emission);
+    let emission_curve = u64::from_le_bytes(args.emission_curve);
+    let emission_interval = u64::from_le_bytes(args.emission_interval);
+    let emission_decay = u64::from_le_bytes(args.emission_decay);
+    let reset_bounty = u64::from_le_bytes(args.reset_bounty);
+    let auth_mode = u64::from_le_bytes(args.auth_mode);
+    let claim_fee_bps = u64::from_le_bytes(args.claim_fee_bps);
+    let reap_period = i64::from_le_bytes(args.reap_period);
+
+    // 加载账户
+    let [signer, config_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    // 加载配置账户，并确保签名者是当前管理员
+    load_config_with_admin(config_info, signer.key, true)?;
+
+    // 验证周期持续时间
+    if epoch_duration.lt(&MIN_EPOCH_DURATION) || epoch_duration.gt(&MAX_EPOCH_DURATION) {
+        return Err(LuckycoinError::ConfigInvalid.into());
+    }
+
+    // 验证容忍时间
+    if tolerance.lt(&0) || tolerance.gt(&MAX_TOLERANCE) {
+        return Err(LuckycoinError::ConfigInvalid.into());
+    }
+
+    // 验证奖励率阈值。最高阈值必须大于最低阈值的两倍，否则难度调整会来回震荡。
+    if base_reward_rate_min_threshold.eq(&0)
+        || base_reward_rate_max_threshold.le(&base_reward_rate_min_threshold.saturating_mul(2))
+    {
+        return Err(LuckycoinError::ConfigInvalid.into());
+    }
+
+    // 验证平滑因子
+    if smoothing_factor.lt(&2) || smoothing_factor.gt(&MAX_SMOOTHING_FACTOR) {
+        return Err(LuckycoinError::ConfigInvalid.into());
+    }
+
+    // 验证单个哈希的奖励上限
+    if max_hash_reward.eq(&0) || max_hash_reward.gt(&BUS_EPOCH_REWARDS) {
+        return Err(LuckycoinError::ConfigInvalid.into());
+    }
+
+    // 验证解除质押冷却时间
+    if unstake_cooldown.lt(&0) || unstake_cooldown.gt(&MAX_UNSTAKE_COOLDOWN) {
+        return Err(LuckycoinError::ConfigInvalid.into());
+    }
+
+    // 验证幸运奖池比例
+    if jackpot_rate.gt(&MAX_JACKPOT_RATE) {
+        return Err(LuckycoinError::ConfigInvalid.into());
+    }
+
+    // 验证幸运奖池难度。为 0 时禁用，否则不能超过哈希的位数。
+    if jackpot_difficulty.gt(&128) {
+        return Err(LuckycoinError::ConfigInvalid.into());
+    }
+
+    // 验证抽奖概率分母
+    if jackpot_odds.lt(&MIN_JACKPOT_ODDS) || jackpot_odds.gt(&MAX_JACKPOT_ODDS) {
+        return Err(LuckycoinError::ConfigInvalid.into());
+    }
+
+    // 验证尾部发行量
+    if tail_emission.gt(&MAX_TAIL_EMISSION) {
+        return Err(LuckycoinError::ConfigInvalid.into());
+    }
+
+    // 验证发行曲线
+    if emission_curve.gt(&EMISSION_CURVE_DECAY)
+        || emission_interval.eq(&0)
+        || emission_decay.eq(&0)
+        || emission_decay.gt(&BPS_DENOMINATOR)
+    {
+        return Err(LuckycoinError::ConfigInvalid.into());
+    }
+
+    // 验证重置赏金
+    if reset_bounty.gt(&MAX_RESET_BOUNTY) {
+        return Err(LuckycoinError::ConfigInvalid.into());
+    }
+
+    // 验证认证模式
+    if auth_mode.gt(&AUTH_MODE_NONE) {
+        return Err(LuckycoinError::ConfigInvalid.into());
+    }
+
+    // 验证领取手续费比例
+    if claim_fee_bps.gt(&MAX_CLAIM_FEE_BPS) {
+        return Err(LuckycoinError::ConfigInvalid.into());
+    }
+
+    // 验证回收闲置时间。为 0 时禁用回收。
+    if reap_period.ne(&0) && reap_period.lt(&MIN_REAP_PERIOD) {
+        return Err(LuckycoinError::ConfigInvalid.into());
+    }
+
+    // 更新配置
+    let mut config_data = config_info.data.borrow_mut();
+    let config = Config::try_from_bytes_mut(&mut config_data)?;
+    config.epoch_duration = epoch_duration;
+    config.tolerance = tolerance;
+    config.base_reward_rate_min_threshold = base_reward_rate_min_threshold;
+    config.base_reward_rate_max_threshold = base_reward_rate_max_threshold;
+    config.smoothing_factor = smoothing_factor;
+    config.max_hash_reward = max_hash_reward;
+    config.unstake_cooldown = unstake_cooldown;
+    config.jackpot_rate = jackpot_rate;
+    config.jackpot_difficulty = jackpot_difficulty;
+    config.jackpot_odds = jackpot_odds;
+    config.tail_emission = tail_emission;
+    config.emission_curve = emission_curve;
+    config.emission_interval = emission_interval;
+    config.emission_decay = emission_decay;
+    config.reset_bounty = reset_bounty;
+    config.auth_mode = auth_mode;
+    config.claim_fee_bps = claim_fee_bps;
+    config.reap_period = reap_period;
+
+    Ok(())
+}
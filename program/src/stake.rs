@@ -1,4 +1,12 @@
-use luckycoin_api::{consts::*, instruction::Stake, loaders::*, state::Proof};
+use luckycoin_api::{
+    consts::*,
+    cpi::{calculate_transfer_fee, emit_event, transfer_checked},
+    error::LuckycoinError,
+    event::{EventHeader, LuckycoinEvent, StakeEvent},
+    instruction::Stake,
+    loaders::*,
+    state::{Config, Proof, Treasury},
+};
 use solana_program::{
     account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
     program_error::ProgramError, sysvar::Sysvar,
@@ -14,12 +22,19 @@ pub fn process_stake(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
         proof_info, 
         sender_info, 
         treasury_tokens_info, 
-        token_program
+        token_program,
+        config_info,
+        event_authority_info,
+        luckycoin_program,
+        mint_info,
+        treasury_info
     ] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     // 验证和加载账户
     load_signer(signer)?;
+    // 加载配置账户
+    load_config(config_info, false)?;
     // 加载并验证证明账户，确保与签名者相关联
     load_proof(proof_info, signer.key, true)?;
     // 加载发送者的代币账户，确保其有效性并与预定义的铸币地址相关联
@@ -29,28 +44,63 @@ pub fn process_stake(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
         &MINT_ADDRESS, 
         true
     )?;
+    // 加载国库账户
+    load_treasury(treasury_info, true)?;
     // 加载国币代币账户
     load_treasury_tokens(treasury_tokens_info, true)?;
-    // 加载代币程序的ID
-    load_program(token_program, spl_token::id())?;
+    // 加载铸币账户
+    load_mint(mint_info, MINT_ADDRESS, false)?;
+    // 加载代币程序，SPL Token 和 Token-2022 均可
+    load_token_program(token_program)?;
+    load_event_authority(event_authority_info)?;
+    load_program(luckycoin_program, luckycoin_api::id())?;
+
+    // 检查质押是否已被暂停
+    let config_data = config_info.data.borrow();
+    let config = Config::try_from_bytes(&config_data)?;
+    if config.is_paused(PAUSE_STAKE) {
+        return Err(LuckycoinError::Paused.into());
+    }
 
-    // 更新证明账户余额
+    // 更新证明账户的质押本金。如果铸币收取转账手续费，只记入国库实际收到的数量。
+    let received = amount
+        .checked_sub(calculate_transfer_fee(mint_info, amount)?)
+        .ok_or(LuckycoinError::Overflow)?;
     let mut proof_data = proof_info.data.borrow_mut();
     let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
-    proof.balance = proof.balance.checked_add(amount).unwrap();
+    proof.stake = proof.stake.checked_add(received).ok_or(LuckycoinError::Overflow)?;
+
+    // 记录财库的负债
+    let mut treasury_data = treasury_info.data.borrow_mut();
+    let treasury = Treasury::try_from_bytes_mut(&mut treasury_data)?;
+    treasury.total_credited = treasury
+        .total_credited
+        .checked_add(received)
+        .ok_or(LuckycoinError::Overflow)?;
+    drop(treasury_data);
 
     // 跟新抵押时间戳(获取当前时间戳，并将其设置为最后抵押的时间戳)
     let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
     proof.last_stake_at = clock.unix_timestamp;
 
     // 转移代币(将制定数量的代币从签名者账户转移到国库代币账户)
-    transfer(
+    transfer_checked(
         signer,
         sender_info,
+        mint_info,
         treasury_tokens_info,
         token_program,
         amount,
     )?;
 
+    // 记录质押事件
+    let event = StakeEvent {
+        header: EventHeader::new(LuckycoinEvent::Stake),
+        authority: proof.authority,
+        amount: received,
+        stake: proof.stake,
+    };
+    emit_event(event_authority_info, luckycoin_program, event.to_bytes())?;
+
     Ok(())
 }
@@ -0,0 +1,37 @@
+use luckycoin_api::{
+    consts::*,
+    error::LuckycoinError,
+    instruction::Unpause,
+    loaders::*,
+    state::Config,
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+use steel::*;
+
+/// Unpause 允许管理员恢复一个或多个已暂停的指令。
+pub fn process_unpause(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // 解析参数
+    let args = Unpause::try_from_bytes(data)?;
+    let flags = u64::from_le_bytes(args.flags);
+
+    // 加载账户
+    let [signer, config_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_config_with_admin(config_info, signer.key, true)?;
+
+    // 验证标志位
+    if flags.eq(&0) || flags & !PAUSE_ALL != 0 {
+        return Err(LuckycoinError::ConfigInvalid.into());
+    }
+
+    // 清除暂停标志位
+    let mut config_data = config_info.data.borrow_mut();
+    let config = Config::try_from_bytes_mut(&mut config_data)?;
+    config.paused &= !flags;
+
+    Ok(())
+}
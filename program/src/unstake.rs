@@ -0,0 +1,186 @@
+use luckycoin_api::{
+    consts::*,
+    cpi::{emit_event, transfer_checked_signed},
+    error::LuckycoinError,
+    event::{EventHeader, LuckycoinEvent, UnstakeEvent},
+    instruction::Unstake,
+    loaders::*,
+    state::{Config, Proof, Treasury},
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program_error::ProgramError, sysvar::Sysvar,
+};
+use steel::*;
+
+/// Unstake 将质押本金从国库退还给矿工。距离最后一次质押必须超过配置的冷却时间。
+pub fn process_unstake(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    let args = Unstake::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    let [
+        signer, 
+        beneficiary_info, 
+        config_info, 
+        proof_info, 
+        treasury_info, 
+        treasury_tokens_info, 
+        token_program,
+        event_authority_info,
+        luckycoin_program,
+        mint_info
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    // 加载受益人的代币账户，确保其与预期的铸币地址匹配。
+    load_token_account(
+        beneficiary_info, 
+        None, 
+        &MINT_ADDRESS, 
+        true
+    )?;
+    // 加载配置账户
+    load_config(config_info, false)?;
+    // 加载与签名者关联的证明账户
+    load_proof(proof_info, signer.key, true)?;
+    // 加载国库账户
+    load_treasury(treasury_info, true)?;
+    // 加载国库代币账户
+    load_treasury_tokens(treasury_tokens_info, true)?;
+    // 加载铸币账户
+    load_mint(mint_info, MINT_ADDRESS, false)?;
+    // 加载代币程序，SPL Token 和 Token-2022 均可
+    load_token_program(token_program)?;
+    load_event_authority(event_authority_info)?;
+    load_program(luckycoin_program, luckycoin_api::id())?;
+
+    // 检查解除质押是否已被暂停
+    let config_data = config_info.data.borrow();
+    let config = Config::try_from_bytes(&config_data)?;
+    if config.is_paused(PAUSE_UNSTAKE) {
+        return Err(LuckycoinError::Paused.into());
+    }
+
+    // 验证冷却时间和锁定期已经结束，并更新矿工的质押本金
+    let mut proof_data = proof_info.data.borrow_mut();
+    let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+    proof.stake = unstake_balance(config, proof, amount, clock.unix_timestamp)?;
+
+    // 记录财库的支出
+    let mut treasury_data = treasury_info.data.borrow_mut();
+    let treasury = Treasury::try_from_bytes_mut(&mut treasury_data)?;
+    treasury.total_debited = treasury
+        .total_debited
+        .checked_add(amount)
+        .ok_or(LuckycoinError::Overflow)?;
+    drop(treasury_data);
+
+    // 从国库向受益人账户转移代币。
+    transfer_checked_signed(
+        treasury_info,
+        treasury_tokens_info,
+        mint_info,
+        beneficiary_info,
+        token_program,
+        amount,
+        &[&[TREASURY, &[TREASURY_BUMP]]],
+    )?;
+
+    // 记录解除质押事件
+    let event = UnstakeEvent {
+        header: EventHeader::new(LuckycoinEvent::Unstake),
+        authority: proof.authority,
+        beneficiary: *beneficiary_info.key,
+        amount,
+        stake: proof.stake,
+    };
+    emit_event(event_authority_info, luckycoin_program, event.to_bytes())?;
+
+    Ok(())
+}
+
+/// 计算在时间 `t` 解除质押 `amount` 之后剩余的质押本金。
+///
+/// 距离最后一次质押不足冷却时间、质押仍在锁定期内，或者金额超过质押本金时返回错误。
+pub fn unstake_balance(
+    config: &Config,
+    proof: &Proof,
+    amount: u64,
+    t: i64,
+) -> Result<u64, LuckycoinError> {
+    // 验证冷却时间已经结束
+    if proof.last_stake_at.saturating_add(config.unstake_cooldown).gt(&t) {
+        return Err(LuckycoinError::UnstakeCooldown);
+    }
+
+    // 锁定期内不能提前解除质押
+    if proof.lock_until.gt(&t) {
+        return Err(LuckycoinError::StakeLocked);
+    }
+
+    proof.stake.checked_sub(amount).ok_or(LuckycoinError::UnstakeTooLarge)
+}
+
+#[cfg(test)]
+mod tests {
+    use luckycoin_api::{
+        consts::{ONE_MINUTE, UNSTAKE_COOLDOWN},
+        error::LuckycoinError,
+        state::{Config, Proof},
+    };
+    use steel::*;
+
+    use super::unstake_balance;
+
+    fn setup() -> (Config, Proof) {
+        let mut config = Config::zeroed();
+        config.unstake_cooldown = UNSTAKE_COOLDOWN;
+        let mut proof = Proof::zeroed();
+        proof.stake = 1_000;
+        proof.last_stake_at = ONE_MINUTE;
+        (config, proof)
+    }
+
+    #[test]
+    fn test_unstake_balance() {
+        let (config, proof) = setup();
+        let t = ONE_MINUTE + UNSTAKE_COOLDOWN;
+        assert_eq!(unstake_balance(&config, &proof, 400, t), Ok(600));
+        assert_eq!(unstake_balance(&config, &proof, 1_000, t), Ok(0));
+    }
+
+    #[test]
+    fn test_unstake_balance_cooldown() {
+        let (config, proof) = setup();
+        let t = ONE_MINUTE + UNSTAKE_COOLDOWN - 1;
+        assert_eq!(
+            unstake_balance(&config, &proof, 400, t),
+            Err(LuckycoinError::UnstakeCooldown)
+        );
+    }
+
+    #[test]
+    fn test_unstake_balance_too_large() {
+        let (config, proof) = setup();
+        let t = ONE_MINUTE + UNSTAKE_COOLDOWN;
+        assert_eq!(
+            unstake_balance(&config, &proof, 1_001, t),
+            Err(LuckycoinError::UnstakeTooLarge)
+        );
+    }
+
+    #[test]
+    fn test_unstake_balance_locked() {
+        let (config, mut proof) = setup();
+        let t = ONE_MINUTE + UNSTAKE_COOLDOWN;
+        proof.lock_tier = 1;
+        proof.lock_until = t + 1;
+        assert_eq!(
+            unstake_balance(&config, &proof, 400, t),
+            Err(LuckycoinError::StakeLocked)
+        );
+        assert_eq!(unstake_balance(&config, &proof, 400, t + 1), Ok(600));
+    }
+}
@@ -1,4 +1,9 @@
-use luckycoin_api::{loaders::*, state::Proof};
+use luckycoin_api::{
+    cpi::emit_event,
+    event::{EventHeader, LuckycoinEvent, UpdateEvent},
+    loaders::*,
+    state::Proof,
+};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
 };
@@ -6,7 +11,7 @@ use steel::*;
 
 pub fn process_update(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
     // Load accounts.
-    let [signer, miner_info, proof_info] = accounts else {
+    let [signer, miner_info, proof_info, event_authority_info, luckycoin_program] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
@@ -15,11 +20,21 @@ pub fn process_update(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResu
     load_any(miner_info, false)?;
     // 加载证明账户，并检查其与签名者的关联性，确保签名者有权更新该证明账户
     load_proof(proof_info, signer.key, true)?;
+    load_event_authority(event_authority_info)?;
+    load_program(luckycoin_program, luckycoin_api::id())?;
 
     // 更新证明账户的矿工权限
     let mut proof_data = proof_info.data.borrow_mut();
     let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
     proof.miner = *miner_info.key;
 
+    // 记录更新事件
+    let event = UpdateEvent {
+        header: EventHeader::new(LuckycoinEvent::Update),
+        authority: proof.authority,
+        miner: proof.miner,
+    };
+    emit_event(event_authority_info, luckycoin_program, event.to_bytes())?;
+
     Ok(())
 }
@@ -0,0 +1,105 @@
+use luckycoin_api::{consts::*, error::LuckycoinError, instruction::UpdateMetadata, loaders::*};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use steel::*;
+
+use crate::initialize::parse_metadata_string;
+
+/// UpdateMetadata 允许管理员在发行后修改代币的名称、符号和 URI。
+///
+/// 早期部署的元数据更新授权者是初始化时的签名者，而不是财库。此时签名者必须是该更新授权者，
+/// 指令在更新元数据的同时将更新授权者移交给财库，之后的更新都由财库签名。
+pub fn process_update_metadata(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // 解析参数
+    let args = UpdateMetadata::try_from_bytes(data)?;
+    let name = parse_metadata_string(&args.name, mpl_token_metadata::MAX_NAME_LENGTH)?;
+    let symbol = parse_metadata_string(&args.symbol, mpl_token_metadata::MAX_SYMBOL_LENGTH)?;
+    let uri = parse_metadata_string(&args.uri, mpl_token_metadata::MAX_URI_LENGTH)?;
+
+    // 加载账户
+    let [signer, config_info, metadata_info, treasury_info, metadata_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    // 加载配置账户，并确保签名者是当前管理员
+    load_config_with_admin(config_info, signer.key, false)?;
+    // 加载铸币的元数据账户
+    if metadata_info.key.ne(&METADATA_ADDRESS) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    load_treasury(treasury_info, false)?;
+    load_program(metadata_program, mpl_token_metadata::ID)?;
+
+    // 确定元数据当前的更新授权者
+    let update_authority = metadata_update_authority(&metadata_info.data.borrow())
+        .ok_or(LuckycoinError::MetadataInvalid)?;
+    let data = mpl_token_metadata::types::DataV2 {
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    };
+    if update_authority.eq(&TREASURY_ADDRESS) {
+        // 财库是元数据的更新授权者
+        mpl_token_metadata::instructions::UpdateMetadataAccountV2Cpi {
+            __program: metadata_program,
+            metadata: metadata_info,
+            update_authority: treasury_info,
+            __args: mpl_token_metadata::instructions::UpdateMetadataAccountV2InstructionArgs {
+                data: Some(data),
+                new_update_authority: None,
+                primary_sale_happened: None,
+                is_mutable: None,
+            },
+        }
+        .invoke_signed(&[&[TREASURY, &[TREASURY_BUMP]]])?;
+    } else if update_authority.eq(signer.key) {
+        // 早期部署由签名者更新，并将更新授权者移交给财库
+        mpl_token_metadata::instructions::UpdateMetadataAccountV2Cpi {
+            __program: metadata_program,
+            metadata: metadata_info,
+            update_authority: signer,
+            __args: mpl_token_metadata::instructions::UpdateMetadataAccountV2InstructionArgs {
+                data: Some(data),
+                new_update_authority: Some(TREASURY_ADDRESS),
+                primary_sale_happened: None,
+                is_mutable: None,
+            },
+        }
+        .invoke()?;
+    } else {
+        return Err(LuckycoinError::MetadataInvalid.into());
+    }
+
+    Ok(())
+}
+
+/// 读取元数据账户的更新授权者。元数据账户以 1 字节的账户类型开头，随后是更新授权者。
+pub fn metadata_update_authority(data: &[u8]) -> Option<Pubkey> {
+    data.get(1..33)
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .map(Pubkey::new_from_array)
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_program::pubkey::Pubkey;
+
+    use super::metadata_update_authority;
+
+    #[test]
+    fn test_metadata_update_authority() {
+        let authority = Pubkey::new_unique();
+        let mut data = vec![4u8];
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        assert_eq!(metadata_update_authority(&data), Some(authority));
+        assert_eq!(metadata_update_authority(&data[..32]), None);
+        assert_eq!(metadata_update_authority(&[]), None);
+    }
+}
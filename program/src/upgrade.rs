@@ -1,11 +1,20 @@
-use luckycoin_api::{consts::*, error::LuckycoinError, instruction::Stake};
+use luckycoin_api::{
+    consts::*,
+    cpi::{emit_event, mint_to_checked_signed},
+    error::LuckycoinError,
+    event::{EventHeader, LuckycoinEvent, UpgradeEvent},
+    instruction::Stake,
+    state::Config,
+};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
-    program_pack::Pack,
 };
-use spl_token::state::Mint;
+use spl_token_2022::{extension::StateWithExtensions, state::Mint};
 use steel::*;
-use luckycoin_api::loaders::{load_mint, load_program, load_signer, load_token_account};
+use luckycoin_api::loaders::{
+    load_config, load_event_authority, load_mint, load_program, load_signer, load_token_account,
+    load_token_program,
+};
 
 pub fn process_upgrade(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     let args = Stake::try_from_bytes(data)?;
@@ -19,7 +28,11 @@ pub fn process_upgrade(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResu
         mint_v1_info, 
         sender_info, 
         treasury_info, 
-        token_program
+        token_program,
+        config_info,
+        event_authority_info,
+        luckycoin_program,
+        token_program_v1
     ] = accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -33,6 +46,8 @@ pub fn process_upgrade(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResu
         &MINT_ADDRESS, 
         true
     )?;
+    // 加载配置账户
+    load_config(config_info, false)?;
     // 加载版本2和版本1的铸币账户。
     load_mint(mint_info, MINT_ADDRESS, true)?;
     load_mint(mint_v1_info, MINT_V1_ADDRESS, true)?;
@@ -43,7 +58,18 @@ pub fn process_upgrade(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResu
         &MINT_V1_ADDRESS, 
         true
     )?;
-    load_program(token_program, spl_token::id())?;
+    // 版本2代币可以使用 SPL Token 或 Token-2022 程序，版本1代币始终使用 SPL Token 程序
+    load_token_program(token_program)?;
+    load_program(token_program_v1, spl_token::id())?;
+    load_event_authority(event_authority_info)?;
+    load_program(luckycoin_program, luckycoin_api::id())?;
+
+    // 检查升级是否已被暂停
+    let config_data = config_info.data.borrow();
+    let config = Config::try_from_bytes(&config_data)?;
+    if config.is_paused(PAUSE_UPGRADE) {
+        return Err(LuckycoinError::Paused.into());
+    }
 
     // 燃烧版本1代币
     solana_program::program::invoke(
@@ -56,7 +82,7 @@ pub fn process_upgrade(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResu
             amount,
         )?,
         &[
-            token_program.clone(),
+            token_program_v1.clone(),
             sender_info.clone(),
             mint_v1_info.clone(),
             signer.clone(),
@@ -69,14 +95,14 @@ pub fn process_upgrade(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResu
 
     // 检查最大供应量(检查当前版本2代币的供应量，确保铸造后的总供应量不超过最大供应量)
     let mint_data = mint_info.data.borrow();
-    let mint = Mint::unpack(&mint_data)?;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?.base;
     if mint.supply.saturating_add(amount_to_mint).gt(&MAX_SUPPLY) {
         return Err(LuckycoinError::MaxSupply.into());
     }
 
     // 铸造版本2代币
     drop(mint_data);
-    mint_to_signed(
+    mint_to_checked_signed(
         mint_info,
         beneficiary_info,
         treasury_info,
@@ -85,5 +111,15 @@ pub fn process_upgrade(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResu
         &[&[TREASURY, &[TREASURY_BUMP]]],
     )?;
 
+    // 记录升级事件
+    let event = UpgradeEvent {
+        header: EventHeader::new(LuckycoinEvent::Upgrade),
+        authority: *signer.key,
+        beneficiary: *beneficiary_info.key,
+        burned: amount,
+        minted: amount_to_mint,
+    };
+    emit_event(event_authority_info, luckycoin_program, event.to_bytes())?;
+
     Ok(())
 }
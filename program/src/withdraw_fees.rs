@@ -0,0 +1,54 @@
+use luckycoin_api::{
+    consts::*,
+    cpi::transfer_checked_signed,
+    instruction::WithdrawFees,
+    loaders::*,
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+use steel::*;
+
+/// WithdrawFees 允许管理员从手续费金库中提取领取时收取的手续费。
+pub fn process_withdraw_fees(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // 解析参数
+    let args = WithdrawFees::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    // 加载账户
+    let [
+        signer,
+        beneficiary_info,
+        config_info,
+        fee_vault_info,
+        fee_vault_tokens_info,
+        mint_info,
+        token_program,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_token_account(beneficiary_info, None, &MINT_ADDRESS, true)?;
+    // 加载配置账户，并确保签名者是当前管理员
+    load_config_with_admin(config_info, signer.key, false)?;
+    if fee_vault_info.key.ne(&FEE_VAULT_ADDRESS) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    load_fee_vault_tokens(fee_vault_tokens_info, true)?;
+    load_mint(mint_info, MINT_ADDRESS, false)?;
+    load_token_program(token_program)?;
+
+    // 从手续费金库向受益人转移代币。余额不足时由代币程序拒绝。
+    transfer_checked_signed(
+        fee_vault_info,
+        fee_vault_tokens_info,
+        mint_info,
+        beneficiary_info,
+        token_program,
+        amount,
+        &[&[FEE_VAULT, &[FEE_VAULT_BUMP]]],
+    )?;
+
+    Ok(())
+}